@@ -1,11 +1,12 @@
 use std::collections::BTreeSet;
 
 use proc_macro2::{Ident, Literal, Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::spanned::Spanned;
 
 use super::{
-    discard_lifetimes, meta_items, ContainerMeta, FieldMeta, Mode, Namespace, VariantMeta,
+    discard_lifetimes, display_fromstr_type, meta_items, option_inner, ContainerMeta, Conversion,
+    FieldMeta, MapLayout, Mode, Namespace, VariantMeta,
 };
 
 pub(crate) fn from_xml(input: &syn::DeriveInput) -> TokenStream {
@@ -14,6 +15,10 @@ pub(crate) fn from_xml(input: &syn::DeriveInput) -> TokenStream {
         Err(e) => return e.to_compile_error(),
     };
 
+    if let Some(conversion) = &meta.from {
+        return deserialize_from(input, conversion, &meta);
+    }
+
     match (&input.data, meta.mode) {
         (syn::Data::Struct(data), None) => match &data.fields {
             syn::Fields::Named(fields) => deserialize_struct(input, fields, meta),
@@ -28,7 +33,11 @@ pub(crate) fn from_xml(input: &syn::DeriveInput) -> TokenStream {
             )
             .to_compile_error(),
         },
+        (syn::Data::Struct(data), Some(Mode::Scalar)) => {
+            deserialize_scalar_struct(input, data, meta)
+        }
         (syn::Data::Enum(data), Some(Mode::Scalar)) => deserialize_scalar_enum(input, data, meta),
+        (syn::Data::Enum(data), Some(Mode::Choice)) => deserialize_choice_enum(input, data, meta),
         (syn::Data::Enum(data), Some(Mode::Forward)) => deserialize_forward_enum(input, data, meta),
         (syn::Data::Struct(_), Some(mode)) => syn::Error::new(
             input.span(),
@@ -119,6 +128,250 @@ fn deserialize_scalar_enum(
     )
 }
 
+/// Newtype structs delegate to the inner type; other structs are parsed using `FromStr`
+fn deserialize_scalar_struct(
+    input: &syn::DeriveInput,
+    data: &syn::DataStruct,
+    meta: ContainerMeta<'_>,
+) -> TokenStream {
+    let mut borrowed = BTreeSet::new();
+    let validate = meta.validate(quote!(&value));
+    let body = match &data.fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let mut no_lifetime_type = fields.unnamed[0].ty.clone();
+            discard_lifetimes(&mut no_lifetime_type, &mut borrowed, false, true);
+            quote!(
+                use ::instant_xml::{Accumulate, Error, FromXml};
+
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let mut value = <#no_lifetime_type as FromXml>::Accumulator::default();
+                <#no_lifetime_type as FromXml>::deserialize(&mut value, field, deserializer)?;
+                let value = match value.try_done(field) {
+                    Ok(value) => Self(value),
+                    Err(Error::MissingValue(_)) => return Ok(()),
+                    Err(e) => return Err(e),
+                };
+
+                #validate
+                *into = Some(value);
+                Ok(())
+            )
+        }
+        _ if meta.validate.is_some() => quote!(
+            ::instant_xml::from_xml_str(into, field, deserializer)?;
+            if let Some(value) = into {
+                #validate
+            }
+
+            Ok(())
+        ),
+        _ => quote!(::instant_xml::from_xml_str(into, field, deserializer)),
+    };
+
+    let ident = &input.ident;
+    let generics = meta.xml_generics(borrowed);
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote!(
+        impl #impl_generics FromXml<'xml> for #ident #ty_generics #where_clause {
+            #[inline]
+            fn matches(id: ::instant_xml::Id<'_>, field: Option<::instant_xml::Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut ::instant_xml::Deserializer<'cx, 'xml>,
+            ) -> ::std::result::Result<(), ::instant_xml::Error> {
+                #body
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: ::instant_xml::Kind = ::instant_xml::Kind::Scalar;
+        }
+    )
+}
+
+/// Deserializes the intermediate type, then converts it into the container type
+fn deserialize_from(
+    input: &syn::DeriveInput,
+    conversion: &Conversion,
+    meta: &ContainerMeta<'_>,
+) -> TokenStream {
+    let (ty, convert) = match conversion {
+        Conversion::From(ty) => (
+            ty,
+            quote!(<Self as ::core::convert::From<#ty>>::from(value)),
+        ),
+        Conversion::TryFrom(ty) => (
+            ty,
+            quote!(
+                <Self as ::core::convert::TryFrom<#ty>>::try_from(value)
+                    .map_err(|err| Error::UnexpectedValue(format!("{err} for {field}")))?
+            ),
+        ),
+    };
+
+    // Any lifetimes the intermediate type borrows must be outlived by the input
+    let mut borrowed = BTreeSet::new();
+    discard_lifetimes(&mut ty.clone(), &mut borrowed, true, true);
+    borrowed.retain(|lt| lt.ident != "_" && lt.ident != "xml");
+
+    let ident = &input.ident;
+    let validate = meta.validate(quote!(&value));
+    let generics = meta.xml_generics(borrowed);
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote!(
+        impl #impl_generics FromXml<'xml> for #ident #ty_generics #where_clause {
+            #[inline]
+            fn matches(id: ::instant_xml::Id<'_>, field: Option<::instant_xml::Id<'_>>) -> bool {
+                <#ty as FromXml<'xml>>::matches(id, field)
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut ::instant_xml::Deserializer<'cx, 'xml>,
+            ) -> ::std::result::Result<(), ::instant_xml::Error> {
+                use ::instant_xml::{Accumulate, Error};
+
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let mut value = <#ty as FromXml<'xml>>::Accumulator::default();
+                <#ty as FromXml<'xml>>::deserialize(&mut value, field, deserializer)?;
+                let value: #ty = match value.try_done(field) {
+                    Ok(value) => value,
+                    Err(Error::MissingValue(_)) => return Ok(()),
+                    Err(e) => return Err(e),
+                };
+
+                let value = #convert;
+                #validate
+                *into = Some(value);
+                Ok(())
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: ::instant_xml::Kind = <#ty as FromXml<'xml>>::KIND;
+            const ANY_NAME: bool = <#ty as FromXml<'xml>>::ANY_NAME;
+        }
+    )
+}
+
+fn deserialize_choice_enum(
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    meta: ContainerMeta<'_>,
+) -> TokenStream {
+    let ident = &input.ident;
+    let mut variants = TokenStream::new();
+
+    for variant in data.variants.iter() {
+        let v_ident = &variant.ident;
+        let meta = match VariantMeta::from_variant(variant, &meta) {
+            Ok(meta) => meta,
+            Err(err) => return err.to_compile_error(),
+        };
+
+        let serialize_as = meta.serialize_as;
+        variants.extend(quote!(#serialize_as => Some(#ident::#v_ident),));
+    }
+
+    // Without an explicit namespace, variant elements inherit the namespace of the
+    // element they are nested in.
+    let variant_ns = match &meta.ns.uri {
+        Some(ns) => quote!(#ns),
+        None => quote!(deserializer.parent().ns),
+    };
+
+    let generics = meta.xml_generics(BTreeSet::new());
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote!(
+        impl #impl_generics FromXml<'xml> for #ident #ty_generics #where_clause {
+            #[inline]
+            fn matches(id: ::instant_xml::Id<'_>, field: Option<::instant_xml::Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut ::instant_xml::Deserializer<'cx, 'xml>,
+            ) -> ::std::result::Result<(), ::instant_xml::Error> {
+                use ::instant_xml::de::Node;
+                use ::instant_xml::Error;
+
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let ns = #variant_ns;
+                let element = loop {
+                    match deserializer.next() {
+                        Some(Ok(Node::Open(element))) => break element,
+                        Some(Ok(Node::Attribute(_))) => continue,
+                        Some(Ok(Node::Text(text))) if text.trim().is_empty() => continue,
+                        Some(Ok(node)) => return Err(Error::UnexpectedNode(format!("{:?} in {}", node, field))),
+                        Some(Err(e)) => return Err(e),
+                        None => return Ok(()),
+                    }
+                };
+
+                let id = deserializer.element_id(&element)?;
+                let value = match id.ns == ns {
+                    true => match id.name {
+                        #variants
+                        _ => None,
+                    },
+                    false => None,
+                };
+
+                let Some(value) = value else {
+                    return Err(Error::UnexpectedTag(
+                        format!("enum variant not found for {:?} in field {}", id, field),
+                    ));
+                };
+
+                let mut nested = deserializer.nested(element);
+                nested.ignore()?;
+
+                // Only whitespace may follow the variant element
+                loop {
+                    match deserializer.next() {
+                        Some(Ok(Node::Text(text))) if text.trim().is_empty() => continue,
+                        Some(Ok(node)) => return Err(Error::UnexpectedNode(format!("{:?} after variant in {}", node, field))),
+                        Some(Err(e)) => return Err(e),
+                        None => break,
+                    }
+                }
+
+                *into = Some(value);
+                Ok(())
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: ::instant_xml::Kind = ::instant_xml::Kind::Scalar;
+        }
+    )
+}
+
 fn deserialize_forward_enum(
     input: &syn::DeriveInput,
     data: &syn::DataEnum,
@@ -131,6 +384,7 @@ fn deserialize_forward_enum(
     let ident = &input.ident;
     let field_str = format!("{ident}::0");
     let mut matches = TokenStream::new();
+    let mut any_name = Vec::new();
     let mut variants = TokenStream::new();
     let mut borrowed = BTreeSet::new();
     for variant in data.variants.iter() {
@@ -162,6 +416,7 @@ fn deserialize_forward_enum(
             matches.extend(quote!(||));
         }
         matches.extend(quote!(<#no_lifetime_type as FromXml>::matches(id, field)));
+        any_name.push(quote!(<#no_lifetime_type as FromXml>::ANY_NAME));
 
         if !variants.is_empty() {
             variants.extend(quote!(else));
@@ -210,6 +465,7 @@ fn deserialize_forward_enum(
 
             type Accumulator = Option<Self>;
             const KIND: ::instant_xml::Kind = ::instant_xml::Kind::Element;
+            const ANY_NAME: bool = false #(|| #any_name)*;
         }
     )
 }
@@ -226,49 +482,27 @@ fn deserialize_struct(
         ))
     }
 
-    // Varying values
-    let mut elements_tokens = Tokens::default();
-    let mut attributes_tokens = Tokens::default();
-
-    // Common values
-    let mut declare_values = TokenStream::new();
-    let mut return_val = TokenStream::new();
-    let mut direct = TokenStream::new();
-
-    let mut borrowed = BTreeSet::new();
-    for (index, field) in fields.named.iter().enumerate() {
-        if !direct.is_empty() {
-            return syn::Error::new(field.span(), "direct field must be the last")
-                .into_compile_error();
-        }
-
-        let field_meta = match FieldMeta::from_field(field, &container_meta) {
-            Ok(meta) => meta,
-            Err(err) => return err.into_compile_error(),
-        };
-
-        let tokens = match field_meta.attribute {
-            true => &mut attributes_tokens,
-            false => &mut elements_tokens,
-        };
-
-        let result = named_field(
-            field,
-            index,
-            &mut declare_values,
-            &mut return_val,
-            tokens,
-            &mut borrowed,
-            &mut direct,
-            field_meta,
-            &input.ident,
-            &container_meta,
-        );
+    let NamedFields {
+        declare_values,
+        return_val,
+        mut direct,
+        elements: elements_tokens,
+        attributes: attributes_tokens,
+        flatten,
+        borrowed,
+        sequence,
+        tag_name,
+        ..
+    } = match NamedFields::new(input, fields, &container_meta, &TokenStream::new()) {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
 
-        if let Err(err) = result {
-            return err.into_compile_error();
-        }
-    }
+    // Flattening only merges attributes and child elements into the parent
+    let flatten_impl = match direct.is_empty() && !tag_name {
+        true => deserialize_flatten(input, fields, &container_meta),
+        false => TokenStream::new(),
+    };
 
     if direct.is_empty() {
         direct.extend(quote!(Node::Text(_) => {
@@ -276,28 +510,58 @@ fn deserialize_struct(
         }));
     }
 
+    // Unknown nodes are offered to flattened fields, if any
+    let (ignore_attribute, ignore_element) = match flatten.is_empty() {
+        true => (
+            quote!({}),
+            quote!({
+                let mut nested = deserializer.nested(data);
+                nested.ignore()?;
+            }),
+        ),
+        false => (
+            quote!({
+                let node = Some(Node::Attribute(attr));
+                #flatten
+                let _ = node;
+            }),
+            quote!({
+                let node = Some(Node::Open(data));
+                #flatten
+                if let Some(Node::Open(data)) = node {
+                    let mut nested = deserializer.nested(data);
+                    nested.ignore()?;
+                }
+            }),
+        ),
+    };
+
+    // Child elements must follow declaration order, although any of them may be skipped
+    let (declare_position, check_order) = match container_meta.ordered {
+        true => ordered(&sequence),
+        false => (TokenStream::new(), TokenStream::new()),
+    };
+
     // Elements
+    let elements_branches = elements_tokens.dispatch(quote!(__Elements::__Ignore));
     let elements_enum = elements_tokens.r#enum;
-    let mut elements_branches = elements_tokens.branches;
     let elem_type_match = elements_tokens.r#match;
-    elements_branches.extend(match elements_branches.is_empty() {
-        true => quote!(__Elements::__Ignore),
-        false => quote!(else { __Elements::__Ignore }),
-    });
 
     // Attributes
+    let attributes_branches = attributes_tokens.dispatch(quote!(__Attributes::__Ignore));
     let attributes_enum = attributes_tokens.r#enum;
-    let mut attributes_branches = attributes_tokens.branches;
     let attr_type_match = attributes_tokens.r#match;
-    attributes_branches.extend(match attributes_branches.is_empty() {
-        true => quote!(__Attributes::__Ignore),
-        false => quote!(else { __Attributes::__Ignore }),
-    });
 
     let ident = &input.ident;
     let ident_str = format!("{ident}");
     let name = container_meta.tag();
     let default_namespace = container_meta.default_namespace();
+    let any_name = container_meta.any_name;
+    let matches = match any_name {
+        true => quote!(true),
+        false => quote!(id == ::instant_xml::Id { ns: #default_namespace, name: #name }),
+    };
+    let validate = container_meta.validate(quote!(&value));
     let generics = container_meta.xml_generics(borrowed);
 
     let (xml_impl_generics, _, _) = generics.split_for_impl();
@@ -307,7 +571,7 @@ fn deserialize_struct(
         impl #xml_impl_generics FromXml<'xml> for #ident #ty_generics #where_clause {
             #[inline]
             fn matches(id: ::instant_xml::Id<'_>, field: Option<::instant_xml::Id<'_>>) -> bool {
-                id == ::instant_xml::Id { ns: #default_namespace, name: #name }
+                #matches
             }
 
             fn deserialize<'cx>(
@@ -329,6 +593,7 @@ fn deserialize_struct(
                 }
 
                 #declare_values
+                #declare_position
                 loop {
                     let node = match deserializer.next() {
                         Some(result) => result?,
@@ -340,36 +605,366 @@ fn deserialize_struct(
                             let id = deserializer.attribute_id(&attr)?;
                             let field = #attributes_branches;
 
-                            match field {
-                                #attr_type_match
-                                __Attributes::__Ignore => {}
-                            }
-                        }
-                        Node::Open(data) => {
-                            let id = deserializer.element_id(&data)?;
-                            let element = #elements_branches;
+                            match field {
+                                #attr_type_match
+                                __Attributes::__Ignore => #ignore_attribute
+                            }
+                        }
+                        Node::Open(data) => {
+                            let id = deserializer.element_id(&data)?;
+                            let element = #elements_branches;
+                            #check_order
+
+                            match element {
+                                #elem_type_match
+                                __Elements::__Ignore => #ignore_element
+                            }
+                        }
+                        #direct
+                        node => return Err(Error::UnexpectedNode(format!("{:?} in {}", node, #ident_str))),
+                    }
+                }
+
+                let value = Self { #return_val };
+                #validate
+                *into = Some(value);
+                Ok(())
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: ::instant_xml::Kind = ::instant_xml::Kind::Element;
+            const ANY_NAME: bool = #any_name;
+        }
+
+        #flatten_impl
+    )
+}
+
+/// Tracks the position of the last child element in `sequence`, rejecting earlier elements
+fn ordered(sequence: &[(Ident, String, String)]) -> (TokenStream, TokenStream) {
+    let mut positions = TokenStream::new();
+    let mut expected = Vec::with_capacity(sequence.len());
+    for (position, (variant, field_str, _)) in sequence.iter().enumerate() {
+        positions.extend(quote!(__Elements::#variant => Some((#position, #field_str)),));
+        let remaining = sequence[position..]
+            .iter()
+            .map(|(_, _, element)| element.as_str())
+            .collect::<Vec<_>>();
+        expected.push(match remaining.split_last() {
+            Some((last, [])) => last.to_string(),
+            Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+            None => unreachable!(),
+        });
+    }
+
+    let declare = quote!(let mut __position = 0;);
+    let check = quote!(
+        let position: Option<(usize, &'static str)> = match element {
+            #positions
+            _ => None,
+        };
+
+        if let Some((position, field)) = position {
+            const EXPECTED: &[&str] = &[#(#expected),*];
+            if position < __position {
+                return Err(Error::OutOfOrder {
+                    element: id.name.to_owned(),
+                    field,
+                    expected: EXPECTED[__position],
+                });
+            }
+
+            __position = position;
+        }
+    );
+
+    (declare, check)
+}
+
+/// Implements `FromXmlFlatten`, keeping field accumulators in a dedicated struct
+fn deserialize_flatten(
+    input: &syn::DeriveInput,
+    fields: &syn::FieldsNamed,
+    container_meta: &ContainerMeta<'_>,
+) -> TokenStream {
+    let NamedFields {
+        return_val,
+        elements: elements_tokens,
+        attributes: attributes_tokens,
+        flatten,
+        acc_field_defs,
+        acc_field_defaults,
+        borrowed,
+        ..
+    } = match NamedFields::new(input, fields, container_meta, &quote!(into.)) {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+
+    let elements_branches = elements_tokens.dispatch(quote!(__Elements::__Ignore));
+    let elements_enum = elements_tokens.r#enum;
+    let elem_type_match = elements_tokens.r#match;
+
+    let attributes_branches = attributes_tokens.dispatch(quote!(__Attributes::__Ignore));
+    let attributes_enum = attributes_tokens.r#enum;
+    let attr_type_match = attributes_tokens.r#match;
+
+    // Without fields of its own, every node is returned to the caller
+    let consumed = match elem_type_match.is_empty() && attr_type_match.is_empty() {
+        true => TokenStream::new(),
+        false => quote!(Ok(None)),
+    };
+
+    let ident = &input.ident;
+    let accumulator = Ident::new(&format!("__{ident}FlattenAccumulator"), Span::call_site());
+    let validate = container_meta.validate(quote!(&value));
+    let generics = container_meta.xml_generics(borrowed);
+
+    let (xml_impl_generics, xml_ty_generics, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let visibility = &input.vis;
+
+    quote!(
+        impl #xml_impl_generics ::instant_xml::FromXmlFlatten<'xml> for #ident #ty_generics #where_clause {
+            fn deserialize_node<'cx>(
+                into: &mut Self::Accumulator,
+                node: ::instant_xml::de::Node<'xml>,
+                deserializer: &mut ::instant_xml::Deserializer<'cx, 'xml>,
+            ) -> ::std::result::Result<Option<::instant_xml::de::Node<'xml>>, ::instant_xml::Error> {
+                use ::instant_xml::de::Node;
+                use ::instant_xml::{Error, FromXml, Id, Kind};
+
+                enum __Elements {
+                    #elements_enum
+                    __Ignore,
+                }
+
+                enum __Attributes {
+                    #attributes_enum
+                    __Ignore,
+                }
+
+                match node {
+                    Node::Attribute(attr) => {
+                        let id = deserializer.attribute_id(&attr)?;
+                        let field = #attributes_branches;
+
+                        match field {
+                            #attr_type_match
+                            __Attributes::__Ignore => {
+                                let node = Some(Node::Attribute(attr));
+                                #flatten
+                                return Ok(node);
+                            }
+                        }
+                    }
+                    Node::Open(data) => {
+                        let id = deserializer.element_id(&data)?;
+                        let element = #elements_branches;
+
+                        match element {
+                            #elem_type_match
+                            __Elements::__Ignore => {
+                                let node = Some(Node::Open(data));
+                                #flatten
+                                return Ok(node);
+                            }
+                        }
+                    }
+                    node => return Ok(Some(node)),
+                }
+
+                #consumed
+            }
+
+            type Accumulator = #accumulator #xml_ty_generics;
+        }
+
+        #[doc(hidden)]
+        #visibility struct #accumulator #xml_impl_generics #where_clause {
+            #acc_field_defs
+            __marker: ::core::marker::PhantomData<(&'xml (), fn() -> #ident #ty_generics)>,
+        }
+
+        impl #xml_impl_generics ::instant_xml::Accumulate<#ident #ty_generics> for #accumulator #xml_ty_generics #where_clause {
+            fn try_done(self, _: &'static str) -> ::std::result::Result<#ident #ty_generics, ::instant_xml::Error> {
+                use ::instant_xml::Accumulate;
+
+                let into = self;
+                let value = #ident { #return_val };
+                #validate
+                Ok(value)
+            }
+        }
+
+        impl #xml_impl_generics Default for #accumulator #xml_ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #acc_field_defaults
+                    __marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+    )
+}
+
+/// Deserialization code for the fields of a struct with named fields
+///
+/// Field accumulators are local variables when `prefix` is empty, or fields of the value
+/// referenced by `prefix` otherwise.
+#[derive(Default)]
+struct NamedFields {
+    declare_values: TokenStream,
+    return_val: TokenStream,
+    direct: TokenStream,
+    elements: Tokens,
+    attributes: Tokens,
+    flatten: TokenStream,
+    acc_field_defs: TokenStream,
+    acc_field_defaults: TokenStream,
+    borrowed: BTreeSet<syn::Lifetime>,
+    /// Element variants, field names and element names, in declaration order
+    sequence: Vec<(Ident, String, String)>,
+    /// Whether a field holds the element's name
+    tag_name: bool,
+}
 
-                            match element {
-                                #elem_type_match
-                                __Elements::__Ignore => {
-                                    let mut nested = deserializer.nested(data);
-                                    nested.ignore()?;
-                                }
-                            }
-                        }
-                        #direct
-                        node => return Err(Error::UnexpectedNode(format!("{:?} in {}", node, #ident_str))),
-                    }
+impl NamedFields {
+    fn new(
+        input: &syn::DeriveInput,
+        fields: &syn::FieldsNamed,
+        container_meta: &ContainerMeta<'_>,
+        prefix: &TokenStream,
+    ) -> Result<Self, TokenStream> {
+        let mut new = Self::default();
+        for (index, field) in fields.named.iter().enumerate() {
+            if !new.direct.is_empty() {
+                return Err(
+                    syn::Error::new(field.span(), "direct field must be the last")
+                        .into_compile_error(),
+                );
+            }
+
+            let field_meta = match FieldMeta::from_field(field, container_meta) {
+                Ok(meta) => meta,
+                Err(err) => return Err(err.into_compile_error()),
+            };
+
+            let field_name = field.ident.as_ref().unwrap();
+            let val_ident = Ident::new(&format!("__value{index}"), Span::call_site());
+            if field_meta.tag_name {
+                if new.tag_name {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "only one tag_name field is allowed",
+                    )
+                    .into_compile_error());
                 }
 
-                *into = Some(Self { #return_val });
-                Ok(())
+                // The name borrows from the input, like the element's identifier. It is captured
+                // up front, since namespace scopes are gone once the element has been closed.
+                let mut ty = field.ty.clone();
+                discard_lifetimes(&mut ty, &mut new.borrowed, true, true);
+                new.declare_values.extend(quote!(
+                    let #val_ident = ::instant_xml::TagName::from_id(deserializer.parent());
+                ));
+                new.return_val.extend(quote!(#field_name: #val_ident,));
+                new.tag_name = true;
+                continue;
+            } else if field_meta.flatten {
+                let field_str = format!("{}::{field_name}", input.ident);
+                let mut no_lifetime_type = field.ty.clone();
+                discard_lifetimes(
+                    &mut no_lifetime_type,
+                    &mut new.borrowed,
+                    field_meta.borrow,
+                    true,
+                );
+
+                let ty = &field.ty;
+                new.declare_values.extend(quote!(
+                    let mut #val_ident = <#no_lifetime_type as ::instant_xml::FromXmlFlatten>::Accumulator::default();
+                ));
+                new.flatten.extend(quote!(
+                    let node = match node {
+                        Some(node) => <#no_lifetime_type as ::instant_xml::FromXmlFlatten>::deserialize_node(
+                            &mut #prefix #val_ident,
+                            node,
+                            deserializer,
+                        )?,
+                        None => None,
+                    };
+                ));
+                new.return_val.extend(quote!(
+                    #field_name: #prefix #val_ident.try_done(#field_str)?,
+                ));
+                new.acc_field_defs.extend(quote!(
+                    #val_ident: <#ty as ::instant_xml::FromXmlFlatten<'xml>>::Accumulator,
+                ));
+                new.acc_field_defaults
+                    .extend(quote!(#val_ident: Default::default(),));
+                continue;
             }
 
-            type Accumulator = Option<Self>;
-            const KIND: ::instant_xml::Kind = ::instant_xml::Kind::Element;
+            let acc_type = match field_meta.display_fromstr {
+                true => display_fromstr_type(&field.ty),
+                false => field.ty.clone(),
+            };
+            new.acc_field_defs.extend(quote!(
+                #val_ident: <#acc_type as FromXml<'xml>>::Accumulator,
+            ));
+            new.acc_field_defaults
+                .extend(quote!(#val_ident: Default::default(),));
+            let encoded_element =
+                field_meta.encoding.is_some() && !field_meta.attribute && !field_meta.direct;
+            if field_meta.wrapper.is_some() || encoded_element {
+                let seen = Ident::new(&format!("__seen{index}"), Span::call_site());
+                new.acc_field_defs.extend(quote!(#seen: bool,));
+                new.acc_field_defaults.extend(quote!(#seen: false,));
+            }
+
+            if !field_meta.attribute && !field_meta.direct {
+                let element = match &field_meta.wrapper {
+                    Some(wrapper) => wrapper.to_token_stream(),
+                    None => field_meta.tag.clone(),
+                };
+
+                new.sequence.push((
+                    Ident::new(&format!("__Value{index}"), Span::call_site()),
+                    format!("{}::{field_name}", input.ident),
+                    match syn::parse2::<syn::LitStr>(element) {
+                        Ok(lit) => lit.value(),
+                        Err(_) => field_name.to_string(),
+                    },
+                ));
+            }
+
+            let tokens = match field_meta.attribute {
+                true => &mut new.attributes,
+                false => &mut new.elements,
+            };
+
+            let result = named_field(
+                field,
+                index,
+                &mut new.declare_values,
+                &mut new.return_val,
+                tokens,
+                &mut new.borrowed,
+                &mut new.direct,
+                field_meta,
+                &input.ident,
+                container_meta,
+                prefix,
+            );
+
+            if let Err(err) = result {
+                return Err(err.into_compile_error());
+            }
         }
-    )
+
+        Ok(new)
+    }
 }
 
 fn deserialize_inline_struct(
@@ -407,6 +1002,8 @@ fn deserialize_inline_struct(
     let mut acc_field_defs = TokenStream::new();
     let mut acc_field_inits = TokenStream::new();
     let mut deserialize = TokenStream::new();
+    let mut deserialize_any = TokenStream::new();
+    let mut any_name = Vec::new();
     let mut acc_field_defaults = TokenStream::new();
     for (index, field) in fields.named.iter().enumerate() {
         let field_meta = match FieldMeta::from_field(field, &meta) {
@@ -417,9 +1014,24 @@ fn deserialize_inline_struct(
         if field_meta.direct {
             return syn::Error::new(field.span(), "inline structs cannot have a direct field")
                 .to_compile_error();
+        } else if field_meta.wrapper.is_some() {
+            return syn::Error::new(field.span(), "inline structs cannot have wrapper fields")
+                .to_compile_error();
         } else if field_meta.attribute {
             return syn::Error::new(field.span(), "inline structs cannot have attribute fields")
                 .to_compile_error();
+        } else if field_meta.display_fromstr {
+            return syn::Error::new(
+                field.span(),
+                "inline structs cannot have display_fromstr fields",
+            )
+            .to_compile_error();
+        } else if field_meta.flatten {
+            return syn::Error::new(field.span(), "inline structs cannot have flatten fields")
+                .to_compile_error();
+        } else if field_meta.tag_name {
+            return syn::Error::new(field.span(), "inline structs cannot have tag_name fields")
+                .to_compile_error();
         }
 
         let result = named_field(
@@ -433,6 +1045,7 @@ fn deserialize_inline_struct(
             field_meta,
             &input.ident,
             &meta,
+            &TokenStream::new(),
         );
 
         let data = match result {
@@ -454,20 +1067,23 @@ fn deserialize_inline_struct(
         acc_field_defs
             .extend(quote!(#field_name: <#field_ty_with_lifetime as FromXml<'xml>>::Accumulator,));
         let field_str = format!("{}::{}", input.ident, data.field_name);
-        acc_field_inits.extend(quote!(#field_name: self.#field_name.try_done(#field_str)?,));
+        let validate_field = data.validate;
+        let check_occurs = data.check_occurs;
+        acc_field_inits.extend(quote!(
+            #field_name: {
+                let value = self.#field_name.try_done(#field_str)?;
+                #validate_field
+                value
+            },
+        ));
         acc_field_defaults.extend(quote!(#field_name: Default::default(),));
 
-        if !deserialize.is_empty() {
-            deserialize.extend(quote!(else));
-        }
-        if let Some(with) = data.deserialize_with {
-            deserialize.extend(
-                quote!(if <#field_ty as FromXml<'xml>>::matches(current, None) {
-                    #with(&mut into.#field_name, #field_str, deserializer)?;
-                }),
-            );
-        } else {
-            deserialize.extend(quote!(if <#field_ty as FromXml<'xml>>::matches(current, None) {
+        let body = match data.deserialize_with {
+            Some(with) => quote!(
+                #with(&mut into.#field_name, #field_str, deserializer)?;
+                #check_occurs
+            ),
+            None => quote!(
                 match <#field_ty as FromXml>::KIND {
                     Kind::Element => {
                         <#field_ty as FromXml>::deserialize(&mut into.#field_name, #field_str, deserializer)?;
@@ -477,13 +1093,31 @@ fn deserialize_inline_struct(
                         deserializer.ignore()?;
                     }
                 }
-            }));
+                #check_occurs
+            ),
+        };
+
+        // Fields matching any name only get elements that no other field matches
+        if !deserialize.is_empty() {
+            deserialize.extend(quote!(else));
         }
+        deserialize.extend(quote!(
+            if !<#field_ty as FromXml<'xml>>::ANY_NAME && <#field_ty as FromXml<'xml>>::matches(current, None) {
+                #body
+            }
+        ));
+        deserialize_any.extend(quote!(
+            else if <#field_ty as FromXml<'xml>>::ANY_NAME && <#field_ty as FromXml<'xml>>::matches(current, None) {
+                #body
+            }
+        ));
+        any_name.push(quote!(<#field_ty as FromXml<'xml>>::ANY_NAME));
     }
 
     // Attributes
     let ident = &input.ident;
     let accumulator = Ident::new(&format!("__{ident}Accumulator"), Span::call_site());
+    let validate = meta.validate(quote!(&value));
     let generics = meta.xml_generics(borrowed);
 
     let (xml_impl_generics, xml_ty_generics, _) = generics.split_for_impl();
@@ -505,13 +1139,14 @@ fn deserialize_inline_struct(
                 use ::instant_xml::Kind;
 
                 let current = deserializer.parent();
-                #deserialize
+                #deserialize #deserialize_any
 
                 Ok(())
             }
 
             type Accumulator = #accumulator #xml_ty_generics;
             const KIND: ::instant_xml::Kind = ::instant_xml::Kind::Element;
+            const ANY_NAME: bool = false #(|| #any_name)*;
         }
 
         #visibility struct #accumulator #xml_ty_generics #where_clause {
@@ -520,9 +1155,11 @@ fn deserialize_inline_struct(
 
         impl #xml_impl_generics ::instant_xml::Accumulate<#ident #ty_generics> for #accumulator #xml_ty_generics #where_clause {
             fn try_done(self, _: &'static str) -> ::std::result::Result<#ident #ty_generics, ::instant_xml::Error> {
-                Ok(#ident {
+                let value = #ident {
                     #acc_field_inits
-                })
+                };
+                #validate
+                Ok(value)
             }
         }
 
@@ -548,8 +1185,11 @@ fn named_field<'a>(
     mut field_meta: FieldMeta,
     type_name: &Ident,
     container_meta: &ContainerMeta<'_>,
+    prefix: &TokenStream,
 ) -> Result<FieldData<'a>, syn::Error> {
     let field_name = field.ident.as_ref().unwrap();
+    let field_str = format!("{type_name}::{field_name}");
+    let validate = validate_field(&field_meta, &field_str);
     let field_tag = field_meta.tag;
     let default_ns = match &field_meta.ns.uri {
         None if field_meta.attribute => &None,
@@ -575,28 +1215,54 @@ fn named_field<'a>(
 
     let mut no_lifetime_type = field.ty.clone();
     discard_lifetimes(&mut no_lifetime_type, borrowed, field_meta.borrow, true);
+    if field_meta.display_fromstr {
+        no_lifetime_type = display_fromstr_type(&no_lifetime_type);
+    }
+
+    let into_field = match (field_meta.display_fromstr, option_inner(&field.ty)) {
+        (false, _) => quote!(),
+        (true, Some(_)) => quote!(
+            let value: #no_lifetime_type = value;
+            let value = value.map(::instant_xml::DisplayFromStr::into_inner);
+        ),
+        (true, None) => quote!(
+            let value: #no_lifetime_type = value;
+            let value = value.into_inner();
+        ),
+    };
 
     let enum_name = Ident::new(&format!("__Value{index}"), Span::call_site());
     if !field_meta.direct {
         tokens.r#enum.extend(quote!(#enum_name,));
 
-        if !tokens.branches.is_empty() {
-            tokens.branches.extend(quote!(else));
-        }
-        tokens.branches.extend(quote!(
-            if <#no_lifetime_type as FromXml>::matches(id, Some(Id { ns: #ns, name: #field_tag }))
-        ));
+        let variant = match field_meta.attribute {
+            true => quote!(__Attributes::#enum_name),
+            false => quote!(__Elements::#enum_name),
+        };
 
-        tokens.branches.extend(match field_meta.attribute {
-            true => quote!({ __Attributes::#enum_name }),
-            false => quote!({ __Elements::#enum_name }),
-        });
+        match &field_meta.wrapper {
+            Some(wrapper) => tokens.branch(quote!(id == (Id { ns: #ns, name: #wrapper })), variant),
+            None => {
+                let matches = quote!(
+                    <#no_lifetime_type as FromXml>::matches(id, Some(Id { ns: #ns, name: #field_tag }))
+                );
+                tokens.branch(
+                    quote!(!<#no_lifetime_type as FromXml>::ANY_NAME && #matches),
+                    variant.clone(),
+                );
+                tokens.any_branch(
+                    quote!(<#no_lifetime_type as FromXml>::ANY_NAME && #matches),
+                    variant,
+                );
+            }
+        }
     }
 
-    let val_name = Ident::new(&format!("__value{index}"), Span::call_site());
+    let val_ident = Ident::new(&format!("__value{index}"), Span::call_site());
     declare_values.extend(quote!(
-        let mut #val_name = <#no_lifetime_type as FromXml>::Accumulator::default();
+        let mut #val_ident = <#no_lifetime_type as FromXml>::Accumulator::default();
     ));
+    let val_name = quote!(#prefix #val_ident);
 
     if field_meta.direct {
         declare_values.extend(quote!(
@@ -620,40 +1286,187 @@ fn named_field<'a>(
         })
         .transpose()?;
 
-    let field_str = format!("{type_name}::{field_name}");
-    if !field_meta.attribute {
-        if let Some(with) = &deserialize_with {
-            if field_meta.direct {
-                return Err(syn::Error::new(
-                    field.span(),
-                    "direct attribute is not supported deserialization functions",
-                ));
-            }
+    if let (Some(encoding), Some(_)) = (field_meta.encoding, &deserialize_with) {
+        return Err(syn::Error::new(
+            field.span(),
+            format!(
+                "{} fields cannot have deserialization functions",
+                encoding.name()
+            ),
+        ));
+    } else if field_meta.map.is_some() {
+        if field_meta.attribute || field_meta.direct || field_meta.encoding.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "map layouts are only supported on element fields",
+            ));
+        } else if deserialize_with.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "map layout fields cannot have deserialization functions",
+            ));
+        }
+    }
+
+    if field_meta.wrapper.is_some() {
+        if field_meta.attribute || field_meta.direct || field_meta.encoding.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "wrapper is only supported on element fields",
+            ));
+        } else if deserialize_with.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "wrapper fields cannot have deserialization functions",
+            ));
+        }
+    }
+
+    let check_occurs = max_occurs_check(field_meta.max_occurs, &val_name, &field_str);
+    let mut absent = TokenStream::new();
+    let deserialize_value = match &field_meta.format {
+        Some(format) => quote!(
+            <#no_lifetime_type as ::instant_xml::FromXmlFormat>::deserialize_format(&mut #val_name, #format, #field_str, &mut nested)?;
+            #check_occurs
+        ),
+        None => quote!(
+            <#no_lifetime_type as FromXml>::deserialize(&mut #val_name, #field_str, &mut nested)?;
+            #check_occurs
+        ),
+    };
+
+    let deserialize_item = match &field_meta.map {
+        Some(MapLayout::KeyAttribute(key)) => quote!(
+            ::instant_xml::map::deserialize_keyed_entry(&mut #val_name, #key, #field_str, &mut nested)?;
+            #check_occurs
+        ),
+        Some(MapLayout::Pair { key, value }) => quote!(
+            ::instant_xml::map::deserialize_pair_entry(&mut #val_name, #key, #value, #field_str, &mut nested)?;
+            #check_occurs
+        ),
+        None => deserialize_value.clone(),
+    };
 
+    if !field_meta.attribute {
+        if let (true, Some(with)) = (field_meta.direct, &deserialize_with) {
+            direct.extend(quote!(
+                Node::Text(text) => {
+                    seen_direct = true;
+                    let mut nested = deserializer.for_node(Node::Text(text));
+                    #with(&mut #val_name, #field_str, &mut nested)?;
+                }
+            ));
+        } else if let Some(with) = &deserialize_with {
             tokens.r#match.extend(quote!(
                 __Elements::#enum_name => {
                     let mut nested = deserializer.nested(data);
                     #with(&mut #val_name, #field_str, &mut nested)?;
+                    nested.ignore()?;
+                    #check_occurs
                 },
             ));
+        } else if let (true, Some(encoding)) = (field_meta.direct, field_meta.encoding) {
+            let deserialize_fn = encoding.deserialize_fn();
+            direct.extend(quote!(
+                Node::Text(text) => {
+                    if seen_direct {
+                        return Err(Error::DuplicateValue(#field_str));
+                    }
+
+                    seen_direct = true;
+                    let mut nested = deserializer.for_node(Node::Text(text));
+                    #deserialize_fn(&mut #val_name, #field_str, &mut nested)?;
+                }
+            ));
         } else if field_meta.direct {
             direct.extend(quote!(
                 Node::Text(text) => {
                     seen_direct = true;
                     let mut nested = deserializer.for_node(Node::Text(text));
-                    <#no_lifetime_type as FromXml>::deserialize(&mut #val_name, #field_str, &mut nested)?;
+                    #deserialize_value
                 }
             ));
+        } else if field_meta.wrapper.is_some() {
+            let seen = Ident::new(&format!("__seen{index}"), Span::call_site());
+            declare_values.extend(quote!(
+                let mut #seen = false;
+            ));
+            let seen = quote!(#prefix #seen);
+
+            // Only a present wrapper element yields `Some`, even if it is empty
+            if option_inner(&field.ty).is_some() {
+                absent.extend(quote!(
+                    let value = match #seen {
+                        true => value,
+                        false => None,
+                    };
+                ));
+            }
+
+            tokens.r#match.extend(quote!(
+                __Elements::#enum_name => {
+                    if #seen {
+                        return Err(Error::DuplicateValue(#field_str));
+                    }
+
+                    #seen = true;
+                    let mut wrapper = deserializer.nested(data);
+                    loop {
+                        let data = match wrapper.next() {
+                            Some(Ok(Node::Open(data))) => data,
+                            Some(Ok(_)) => continue,
+                            Some(Err(e)) => return Err(e),
+                            None => break,
+                        };
+
+                        let id = wrapper.element_id(&data)?;
+                        let mut nested = wrapper.nested(data);
+                        if <#no_lifetime_type as FromXml>::matches(id, Some(Id { ns: #ns, name: #field_tag })) {
+                            #deserialize_item
+                        }
+
+                        nested.ignore()?;
+                    }
+                },
+            ));
+        } else if field_meta.map.is_some() {
+            tokens.r#match.extend(quote!(
+                __Elements::#enum_name => {
+                    let mut nested = deserializer.nested(data);
+                    #deserialize_item
+                },
+            ));
+        } else if let Some(encoding) = field_meta.encoding {
+            // Decoded values may be empty, so track presence separately
+            let seen = Ident::new(&format!("__seen{index}"), Span::call_site());
+            declare_values.extend(quote!(
+                let mut #seen = false;
+            ));
+            let seen = quote!(#prefix #seen);
+
+            let deserialize_fn = encoding.deserialize_fn();
+            tokens.r#match.extend(quote!(
+                __Elements::#enum_name => {
+                    if #seen {
+                        return Err(Error::DuplicateValue(#field_str));
+                    }
+
+                    #seen = true;
+                    let mut nested = deserializer.nested(data);
+                    #deserialize_fn(&mut #val_name, #field_str, &mut nested)?;
+                    nested.ignore()?;
+                },
+            ));
         } else {
             tokens.r#match.extend(quote!(
                 __Elements::#enum_name => match <#no_lifetime_type as FromXml>::KIND {
                     Kind::Element => {
                         let mut nested = deserializer.nested(data);
-                        <#no_lifetime_type as FromXml>::deserialize(&mut #val_name, #field_str, &mut nested)?;
+                        #deserialize_value
                     }
                     Kind::Scalar => {
                         let mut nested = deserializer.nested(data);
-                        <#no_lifetime_type as FromXml>::deserialize(&mut #val_name, #field_str, &mut nested)?;
+                        #deserialize_value
                         nested.ignore()?;
                     }
                 },
@@ -667,10 +1480,18 @@ fn named_field<'a>(
             ));
         }
 
-        if let Some(with) = &deserialize_with {
+        if let Some(encoding) = field_meta.encoding {
+            let deserialize_fn = encoding.deserialize_fn();
             tokens.r#match.extend(quote!(
                 __Attributes::#enum_name => {
-                    let mut nested = deserializer.nested(data);
+                    let mut nested = deserializer.for_node(Node::AttributeValue(attr.value));
+                    #deserialize_fn(&mut #val_name, #field_str, &mut nested)?;
+                },
+            ));
+        } else if let Some(with) = &deserialize_with {
+            tokens.r#match.extend(quote!(
+                __Attributes::#enum_name => {
+                    let mut nested = deserializer.for_node(Node::AttributeValue(attr.value));
                     #with(&mut #val_name, #field_str, &mut nested)?;
                 },
             ));
@@ -678,7 +1499,7 @@ fn named_field<'a>(
             tokens.r#match.extend(quote!(
                 __Attributes::#enum_name => {
                     let mut nested = deserializer.for_node(Node::AttributeValue(attr.value));
-                    let new = <#no_lifetime_type as FromXml>::deserialize(&mut #val_name, #field_str, &mut nested)?;
+                    #deserialize_value
                 },
             ));
         }
@@ -686,19 +1507,38 @@ fn named_field<'a>(
 
     if !field_meta.direct {
         return_val.extend(quote!(
-            #field_name: #val_name.try_done(#field_str)?,
+            #field_name: {
+                let value = #val_name.try_done(#field_str)?;
+                #absent
+                #into_field
+                #validate
+                value
+            },
         ));
     } else {
+        // Content-less elements are deserialized as empty text
+        let deserialize_empty = match &deserialize_with {
+            Some(with) => quote!(#with(&mut acc, #field_str, &mut nested)?;),
+            None => quote!(
+                <#no_lifetime_type as FromXml>::deserialize(&mut acc, #field_str, &mut nested)?;
+            ),
+        };
+
         return_val.extend(quote!(
-            #field_name: match #val_name.try_done(#field_str) {
-                Ok(value) => value,
-                Err(Error::MissingValue(_)) => {
-                    let mut acc = <#no_lifetime_type as FromXml>::Accumulator::default();
-                    let mut nested = deserializer.for_node(Node::Text("".into()));
-                    <#no_lifetime_type as FromXml>::deserialize(&mut acc, #field_str, &mut nested)?;
-                    acc.try_done(#field_str)?
-                }
-                Err(e) => return Err(e),
+            #field_name: {
+                let value = match #val_name.try_done(#field_str) {
+                    Ok(value) => value,
+                    Err(Error::MissingValue(_)) => {
+                        let mut acc = <#no_lifetime_type as FromXml>::Accumulator::default();
+                        let mut nested = deserializer.for_node(Node::Text("".into()));
+                        #deserialize_empty
+                        acc.try_done(#field_str)?
+                    }
+                    Err(e) => return Err(e),
+                };
+                #into_field
+                #validate
+                value
             }
         ));
     }
@@ -707,6 +1547,12 @@ fn named_field<'a>(
         field_name,
         no_lifetime_type,
         deserialize_with,
+        validate,
+        check_occurs: max_occurs_check(
+            field_meta.max_occurs,
+            &quote!(into.#field_name),
+            &field_str,
+        ),
     })
 }
 
@@ -714,6 +1560,49 @@ struct FieldData<'a> {
     field_name: &'a Ident,
     no_lifetime_type: syn::Type,
     deserialize_with: Option<syn::Path>,
+    validate: TokenStream,
+    check_occurs: TokenStream,
+}
+
+/// Checks `max_occurs` against the accumulator `acc` after each repeated element, so that
+/// oversized input is rejected early
+fn max_occurs_check(max: Option<usize>, acc: &TokenStream, field_str: &str) -> TokenStream {
+    match max {
+        Some(max) => quote!(::instant_xml::validate::max_occurs(&#acc, #max, #field_str)?;),
+        None => TokenStream::new(),
+    }
+}
+
+/// Checks the `min_len`, `max_len`, `min_occurs` and `pattern` facets against the deserialized `value`
+fn validate_field(field_meta: &FieldMeta, field_str: &str) -> TokenStream {
+    let mut validate = TokenStream::new();
+    if let Some(min) = &field_meta.min_len {
+        validate.extend(quote!(
+            ::instant_xml::validate::min_len(&value, #min, #field_str)?;
+        ));
+    }
+
+    if let Some(max) = &field_meta.max_len {
+        validate.extend(quote!(
+            ::instant_xml::validate::max_len(&value, #max, #field_str)?;
+        ));
+    }
+
+    if let Some(min) = field_meta.min_occurs {
+        validate.extend(quote!(
+            ::instant_xml::validate::min_occurs(&value, #min, #field_str)?;
+        ));
+    }
+
+    if let Some(pattern) = &field_meta.pattern {
+        validate.extend(quote!({
+            static REGEX: ::std::sync::OnceLock<::instant_xml::validate::Regex> =
+                ::std::sync::OnceLock::new();
+            ::instant_xml::validate::pattern(&value, &REGEX, #pattern, #field_str)?;
+        }));
+    }
+
+    validate
 }
 
 fn deserialize_tuple_struct(
@@ -730,30 +1619,52 @@ fn deserialize_tuple_struct(
 
     // Varying values
     let mut declare_values = TokenStream::new();
+    let mut attributes = TokenStream::new();
+    let mut content = TokenStream::new();
     let mut return_val = TokenStream::new();
     let mut borrowed = BTreeSet::new();
+    let mut direct = false;
     for (index, field) in fields.unnamed.iter().enumerate() {
-        if !field.attrs.is_empty() {
-            return syn::Error::new(
-                field.span(),
-                "attributes not allowed on tuple struct fields",
-            )
-            .to_compile_error();
+        let field_meta = match FieldMeta::from_field(field, &container_meta) {
+            Ok(meta) => meta,
+            Err(err) => return err.into_compile_error(),
+        };
+
+        if direct && !field_meta.attribute {
+            return syn::Error::new(field.span(), "direct field must be the last")
+                .into_compile_error();
         }
 
-        unnamed_field(
+        direct = field_meta.direct;
+        let result = unnamed_field(
             field,
             index,
+            field_meta,
             &mut declare_values,
+            &mut attributes,
+            &mut content,
             &mut return_val,
             &mut borrowed,
             &input.ident,
+            &container_meta,
+        );
+
+        if let Err(err) = result {
+            return err.into_compile_error();
+        }
+    }
+
+    if !attributes.is_empty() {
+        attributes = quote!(
+            let id = deserializer.attribute_id(&attr)?;
+            #attributes
         );
     }
 
     let ident = &input.ident;
     let name = container_meta.tag();
     let default_namespace = container_meta.default_namespace();
+    let validate = container_meta.validate(quote!(&value));
     let generics = container_meta.xml_generics(borrowed);
 
     let (xml_impl_generics, _, _) = generics.split_for_impl();
@@ -775,9 +1686,29 @@ fn deserialize_tuple_struct(
                 use ::instant_xml::{Accumulate, Error, FromXml, Id, Kind};
 
                 #declare_values
+
+                // Attributes precede the element's content
+                loop {
+                    let attr = match deserializer.next() {
+                        Some(Ok(Node::Attribute(attr))) => attr,
+                        Some(Ok(node)) => {
+                            // Put the first content node back for the fields below
+                            deserializer.push_back(node);
+                            break;
+                        }
+                        Some(Err(e)) => return Err(e),
+                        None => break,
+                    };
+
+                    #attributes
+                }
+
+                #content
                 deserializer.ignore()?;
 
-                *into = Some(Self(#return_val));
+                let value = Self(#return_val);
+                #validate
+                *into = Some(value);
                 Ok(())
             }
 
@@ -791,47 +1722,117 @@ fn deserialize_tuple_struct(
 fn unnamed_field(
     field: &syn::Field,
     index: usize,
+    field_meta: FieldMeta,
     declare_values: &mut TokenStream,
+    attributes: &mut TokenStream,
+    content: &mut TokenStream,
     return_val: &mut TokenStream,
     borrowed: &mut BTreeSet<syn::Lifetime>,
     type_name: &Ident,
-) {
+    container_meta: &ContainerMeta<'_>,
+) -> Result<(), syn::Error> {
     let mut no_lifetime_type = field.ty.clone();
     discard_lifetimes(&mut no_lifetime_type, borrowed, false, true);
 
     let name = Ident::new(&format!("v{index}"), Span::call_site());
     let field_str = format!("{type_name}::{index}");
     declare_values.extend(quote!(
-        let #name = match <#no_lifetime_type as FromXml>::KIND {
-            Kind::Element => match deserializer.next() {
+        let mut #name = <#no_lifetime_type as FromXml>::Accumulator::default();
+    ));
+
+    let deserialize = match &field_meta.deserialize_with {
+        Some(with) => {
+            let path = with.to_string();
+            let path = syn::parse_str::<syn::Path>(path.trim_matches('"')).map_err(|err| {
+                syn::Error::new(
+                    with.span(),
+                    format!("failed to parse deserialize_with as path: {err}"),
+                )
+            })?;
+            quote!(#path)
+        }
+        None => quote!(<#no_lifetime_type as FromXml>::deserialize),
+    };
+
+    let tag = &field_meta.tag;
+    let ns = match (&field_meta.ns.uri, field_meta.attribute) {
+        (Some(Namespace::Path(path)), _) => quote!(#path),
+        (Some(Namespace::Literal(ns)), _) => quote!(#ns),
+        (None, true) => quote!(""),
+        (None, false) => container_meta.default_namespace(),
+    };
+
+    if field_meta.attribute {
+        if !attributes.is_empty() {
+            attributes.extend(quote!(else));
+        }
+
+        attributes.extend(quote!(
+            if id == (Id { ns: #ns, name: #tag }) {
+                let mut nested = deserializer.for_node(Node::AttributeValue(attr.value));
+                #deserialize(&mut #name, #field_str, &mut nested)?;
+            }
+        ));
+    } else if !tag.is_empty() {
+        // Renamed fields are wrapped in an element, even for scalar types
+        content.extend(quote!(
+            match deserializer.next() {
                 Some(Ok(Node::Open(data))) => {
+                    let id = deserializer.element_id(&data)?;
+                    if !<#no_lifetime_type as FromXml>::matches(id, Some(Id { ns: #ns, name: #tag })) {
+                        return Err(Error::UnexpectedTag(format!("{} for {}", id.name, #field_str)));
+                    }
+
                     let mut nested = deserializer.nested(data);
-                    let mut value = <#no_lifetime_type as FromXml>::Accumulator::default();
-                    <#no_lifetime_type as FromXml>::deserialize(&mut value, #field_str, &mut nested)?;
+                    #deserialize(&mut #name, #field_str, &mut nested)?;
                     nested.ignore()?;
-                    value
                 }
                 Some(Ok(node)) => return Err(Error::UnexpectedNode(format!("{:?}", node))),
                 Some(Err(e)) => return Err(e),
-                None => return Err(Error::MissingValue(#field_str)),
+                None => {}
             }
-            Kind::Scalar => {
-                let mut value = <#no_lifetime_type as FromXml>::Accumulator::default();
-                <#no_lifetime_type as FromXml>::deserialize(&mut value, #field_str, deserializer)?;
-                value
+        ));
+    } else if field_meta.deserialize_with.is_some() {
+        content.extend(quote!(
+            #deserialize(&mut #name, #field_str, deserializer)?;
+        ));
+    } else {
+        content.extend(quote!(
+            match <#no_lifetime_type as FromXml>::KIND {
+                Kind::Element => match deserializer.next() {
+                    Some(Ok(Node::Open(data))) => {
+                        let id = deserializer.element_id(&data)?;
+                        if !<#no_lifetime_type as FromXml>::matches(id, None) {
+                            return Err(Error::UnexpectedTag(format!("{} for {}", id.name, #field_str)));
+                        }
+
+                        let mut nested = deserializer.nested(data);
+                        <#no_lifetime_type as FromXml>::deserialize(&mut #name, #field_str, &mut nested)?;
+                        nested.ignore()?;
+                    }
+                    Some(Ok(node)) => return Err(Error::UnexpectedNode(format!("{:?}", node))),
+                    Some(Err(e)) => return Err(e),
+                    None => return Err(Error::MissingValue(#field_str)),
+                }
+                Kind::Scalar => {
+                    <#no_lifetime_type as FromXml>::deserialize(&mut #name, #field_str, deserializer)?;
+                }
             }
-        };
-    ));
+        ));
+    }
 
     return_val.extend(quote!(
         #name.try_done(#field_str)?,
     ));
+
+    Ok(())
 }
 
 fn deserialize_unit_struct(input: &syn::DeriveInput, meta: &ContainerMeta<'_>) -> TokenStream {
     let ident = &input.ident;
     let name = meta.tag();
     let default_namespace = meta.default_namespace();
+    let validate = meta.validate(quote!(&value));
     let generics = meta.xml_generics(BTreeSet::new());
 
     let (xml_impl_generics, _, _) = generics.split_for_impl();
@@ -850,7 +1851,9 @@ fn deserialize_unit_struct(input: &syn::DeriveInput, meta: &ContainerMeta<'_>) -
                 deserializer: &mut ::instant_xml::Deserializer<'cx, 'xml>,
             ) -> ::std::result::Result<(), ::instant_xml::Error> {
                 deserializer.ignore()?;
-                *into = Some(Self);
+                let value = Self;
+                #validate
+                *into = Some(value);
                 Ok(())
             }
 
@@ -923,5 +1926,34 @@ fn ungroup(mut ty: &syn::Type) -> &syn::Type {
 struct Tokens {
     r#enum: TokenStream,
     branches: TokenStream,
+    /// Branches for types that match any name, tried after all others
+    any_branches: TokenStream,
     r#match: TokenStream,
 }
+
+impl Tokens {
+    fn branch(&mut self, condition: TokenStream, variant: TokenStream) {
+        if !self.branches.is_empty() {
+            self.branches.extend(quote!(else));
+        }
+        self.branches.extend(quote!(if #condition { #variant }));
+    }
+
+    fn any_branch(&mut self, condition: TokenStream, variant: TokenStream) {
+        self.any_branches
+            .extend(quote!(else if #condition { #variant }));
+    }
+
+    /// The expression selecting the variant for a node, or `ignore` if no field matches
+    ///
+    /// Fields whose type matches any name only get nodes that no other field matches.
+    fn dispatch(&self, ignore: TokenStream) -> TokenStream {
+        match self.branches.is_empty() {
+            true => ignore,
+            false => {
+                let (branches, any_branches) = (&self.branches, &self.any_branches);
+                quote!(#branches #any_branches else { #ignore })
+            }
+        }
+    }
+}
@@ -45,6 +45,11 @@ struct ContainerMeta<'input> {
     rename_all: RenameRule,
     mode: Option<Mode>,
     force_prefix: bool,
+    ordered: bool,
+    any_name: bool,
+    from: Option<Conversion>,
+    into: Option<syn::Type>,
+    validate: Option<syn::Path>,
 }
 
 impl<'input> ContainerMeta<'input> {
@@ -54,6 +59,10 @@ impl<'input> ContainerMeta<'input> {
         let mut rename_all = Default::default();
         let mut mode = None;
         let mut force_prefix = false;
+        let mut ordered = false;
+        let mut any_name = false;
+        let (mut from, mut into) = (None, None);
+        let mut validate = None;
 
         for (item, span) in meta_items(&input.attrs) {
             match item {
@@ -79,6 +88,53 @@ impl<'input> ContainerMeta<'input> {
                         force_prefix = true;
                     }
                 }
+                MetaItem::Ordered => match input.data {
+                    syn::Data::Struct(syn::DataStruct {
+                        fields: syn::Fields::Named(_),
+                        ..
+                    }) => ordered = true,
+                    _ => {
+                        return Err(syn::Error::new(
+                            span,
+                            "ordered is only allowed on structs with named fields",
+                        ))
+                    }
+                },
+                MetaItem::AnyName => match input.data {
+                    syn::Data::Struct(syn::DataStruct {
+                        fields: syn::Fields::Named(_),
+                        ..
+                    }) => any_name = true,
+                    _ => {
+                        return Err(syn::Error::new(
+                            span,
+                            "any_name is only allowed on structs with named fields",
+                        ))
+                    }
+                },
+                MetaItem::From(lit) | MetaItem::TryFrom(lit) if from.is_some() => {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "only one of 'from' and 'try_from' can be used",
+                    ))
+                }
+                MetaItem::From(lit) => from = Some(Conversion::From(conversion_type(&lit)?)),
+                MetaItem::TryFrom(lit) => from = Some(Conversion::TryFrom(conversion_type(&lit)?)),
+                MetaItem::Into(lit) => into = Some(conversion_type(&lit)?),
+                MetaItem::Validate(lit) => {
+                    if matches!(input.data, syn::Data::Enum(_)) {
+                        return Err(syn::Error::new(span, "validate is not allowed on enums"));
+                    }
+
+                    validate = Some(syn::parse_str(lit.to_string().trim_matches('"')).map_err(
+                        |err| {
+                            syn::Error::new(
+                                lit.span(),
+                                format!("failed to parse validate as path: {err}"),
+                            )
+                        },
+                    )?);
+                }
                 _ => {
                     return Err(syn::Error::new(
                         span,
@@ -95,6 +151,11 @@ impl<'input> ContainerMeta<'input> {
             rename_all,
             mode,
             force_prefix,
+            ordered,
+            any_name,
+            from,
+            into,
+            validate,
         })
     }
 
@@ -120,6 +181,23 @@ impl<'input> ContainerMeta<'input> {
         }
     }
 
+    /// Calls the container's validation function, if any, on the deserialized `value`
+    fn validate(&self, value: TokenStream) -> TokenStream {
+        let Some(path) = &self.validate else {
+            return TokenStream::new();
+        };
+
+        let ident_str = self.input.ident.to_string();
+        quote!(
+            if let Err(err) = #path(#value) {
+                return Err(::instant_xml::Error::Validation {
+                    field: #ident_str,
+                    message: err.to_string(),
+                });
+            }
+        )
+    }
+
     fn default_namespace(&self) -> TokenStream {
         match &self.ns.uri {
             Some(ns) => quote!(#ns),
@@ -133,32 +211,82 @@ struct FieldMeta {
     attribute: bool,
     borrow: bool,
     direct: bool,
+    display_fromstr: bool,
+    encoding: Option<Encoding>,
+    flatten: bool,
     ns: NamespaceMeta,
     tag: TokenStream,
     serialize_with: Option<Literal>,
     deserialize_with: Option<Literal>,
+    format: Option<Literal>,
+    wrapper: Option<Literal>,
+    map: Option<MapLayout>,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    pattern: Option<Literal>,
+    min_occurs: Option<usize>,
+    max_occurs: Option<usize>,
+    tag_name: bool,
 }
 
 impl FieldMeta {
     fn from_field(input: &syn::Field, container: &ContainerMeta<'_>) -> Result<Self, syn::Error> {
-        let field_name = input.ident.as_ref().unwrap();
+        // Unnamed fields have no tag unless renamed
         let mut meta = Self {
-            tag: container
-                .rename_all
-                .apply_to_field(field_name)
-                .into_token_stream(),
+            tag: match &input.ident {
+                Some(field_name) => container
+                    .rename_all
+                    .apply_to_field(field_name)
+                    .into_token_stream(),
+                None => TokenStream::new(),
+            },
             ..Default::default()
         };
 
+        let (mut key_element, mut value_element) = (None, None);
+        let (mut format_span, mut display_fromstr_span) = (None, None);
+        let (mut with, mut flatten_span) = (None, None);
+        let (mut occurs_span, mut tag_name_span) = (None, None);
         for (item, span) in meta_items(&input.attrs) {
             match item {
                 MetaItem::Attribute => meta.attribute = true,
                 MetaItem::Borrow => meta.borrow = true,
                 MetaItem::Direct => meta.direct = true,
+                MetaItem::DisplayFromStr => {
+                    meta.display_fromstr = true;
+                    display_fromstr_span = Some(span);
+                }
+                MetaItem::Encoding(encoding) => {
+                    if let Some(existing) = meta.encoding {
+                        return Err(syn::Error::new(
+                            span,
+                            format!(
+                                "'{}' and '{}' cannot be combined",
+                                existing.name(),
+                                encoding.name()
+                            ),
+                        ));
+                    }
+
+                    meta.encoding = Some(encoding);
+                }
+                MetaItem::Flatten => {
+                    meta.flatten = true;
+                    flatten_span = Some(span);
+                }
                 MetaItem::Ns(ns) => meta.ns = ns,
                 MetaItem::Rename(lit) => meta.tag = quote!(#lit),
+                MetaItem::With(lit) => with = Some((lit, span)),
                 MetaItem::SerializeWith(lit) => meta.serialize_with = Some(lit),
                 MetaItem::DeserializeWith(lit) => meta.deserialize_with = Some(lit),
+                MetaItem::Format(lit) => {
+                    meta.format = Some(lit);
+                    format_span = Some(span);
+                }
+                MetaItem::Wrapper(lit) => meta.wrapper = Some(lit),
+                MetaItem::KeyAttribute(lit) => meta.map = Some(MapLayout::KeyAttribute(lit)),
+                MetaItem::KeyElement(lit) => key_element = Some((lit, span)),
+                MetaItem::ValueElement(lit) => value_element = Some((lit, span)),
                 MetaItem::RenameAll(_) => {
                     return Err(syn::Error::new(
                         span,
@@ -174,6 +302,204 @@ impl FieldMeta {
                         "attribute 'force_prefix' invalid in field xml attribute",
                     ))
                 }
+                MetaItem::Ordered | MetaItem::AnyName => {
+                    return Err(syn::Error::new(
+                        span,
+                        "container attributes are invalid in field xml attribute",
+                    ))
+                }
+                MetaItem::TagName => {
+                    meta.tag_name = true;
+                    tag_name_span = Some(span);
+                }
+                MetaItem::From(_) | MetaItem::TryFrom(_) | MetaItem::Into(_) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "conversion attributes are only valid on containers",
+                    ))
+                }
+                MetaItem::Validate(_) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "attribute 'validate' invalid in field xml attribute",
+                    ))
+                }
+                MetaItem::MinLen(lit) => meta.min_len = Some(usize_value(lit, "min_len")?),
+                MetaItem::MaxLen(lit) => meta.max_len = Some(usize_value(lit, "max_len")?),
+                MetaItem::Pattern(lit) => meta.pattern = Some(lit),
+                MetaItem::MinOccurs(lit) => {
+                    meta.min_occurs = Some(usize_value(lit, "min_occurs")?);
+                    occurs_span = Some(span);
+                }
+                MetaItem::MaxOccurs(lit) => {
+                    meta.max_occurs = Some(usize_value(lit, "max_occurs")?);
+                    occurs_span = Some(span);
+                }
+            }
+        }
+
+        meta.map = match (meta.map.take(), key_element, value_element) {
+            (map, None, None) => map,
+            (None, Some((key, _)), Some((value, _))) => Some(MapLayout::Pair { key, value }),
+            (Some(_), Some((_, span)), _) => {
+                return Err(syn::Error::new(
+                    span,
+                    "'key_attribute' and 'key_element' cannot be combined",
+                ))
+            }
+            (_, Some((_, span)), None) => {
+                return Err(syn::Error::new(
+                    span,
+                    "'key_element' requires a 'value_element' attribute",
+                ))
+            }
+            (_, None, Some((_, span))) => {
+                return Err(syn::Error::new(
+                    span,
+                    "'value_element' requires a 'key_element' attribute",
+                ))
+            }
+        };
+
+        if let Some((module, span)) = with {
+            if meta.serialize_with.is_some() || meta.deserialize_with.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "'with' cannot be combined with 'serialize_with' or 'deserialize_with'",
+                ));
+            }
+
+            let module = module.to_string();
+            let module = module.trim_matches('"');
+            let mut serialize = Literal::string(&format!("{module}::serialize"));
+            serialize.set_span(span);
+            let mut deserialize = Literal::string(&format!("{module}::deserialize"));
+            deserialize.set_span(span);
+            meta.serialize_with = Some(serialize);
+            meta.deserialize_with = Some(deserialize);
+        }
+
+        // Options that cannot be combined with 'flatten' or 'tag_name'
+        let options = [
+            ("attribute", meta.attribute),
+            ("direct", meta.direct),
+            ("display_fromstr", meta.display_fromstr),
+            ("encoding", meta.encoding.is_some()),
+            ("flatten", meta.flatten),
+            ("ns", meta.ns.uri.is_some()),
+            ("serialize_with", meta.serialize_with.is_some()),
+            ("deserialize_with", meta.deserialize_with.is_some()),
+            ("format", meta.format.is_some()),
+            ("wrapper", meta.wrapper.is_some()),
+            (
+                "key_attribute",
+                matches!(meta.map, Some(MapLayout::KeyAttribute(_))),
+            ),
+            (
+                "key_element",
+                matches!(meta.map, Some(MapLayout::Pair { .. })),
+            ),
+            ("min_len", meta.min_len.is_some()),
+            ("max_len", meta.max_len.is_some()),
+            ("pattern", meta.pattern.is_some()),
+            ("min_occurs", meta.min_occurs.is_some()),
+            ("max_occurs", meta.max_occurs.is_some()),
+        ];
+
+        if let Some(span) = flatten_span {
+            borrow_only("flatten", span, &options)?;
+        }
+
+        if let Some(span) = tag_name_span {
+            borrow_only("tag_name", span, &options)?;
+        }
+
+        if let Some(span) = occurs_span {
+            if meta.attribute || meta.direct || meta.encoding.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "'min_occurs' and 'max_occurs' are only supported on repeated element fields",
+                ));
+            }
+
+            if let (Some(min), Some(max)) = (meta.min_occurs, meta.max_occurs) {
+                if min > max {
+                    return Err(syn::Error::new(
+                        span,
+                        "'min_occurs' cannot be larger than 'max_occurs'",
+                    ));
+                }
+            }
+        }
+
+        if let Some(span) = display_fromstr_span {
+            let conflict = if let Some(encoding) = meta.encoding {
+                Some(format!(
+                    "'display_fromstr' and '{}' cannot be combined",
+                    encoding.name()
+                ))
+            } else if meta.format.is_some() {
+                Some("'display_fromstr' and 'format' cannot be combined".to_owned())
+            } else if meta.map.is_some() || meta.wrapper.is_some() {
+                Some("'display_fromstr' is not supported on collection fields".to_owned())
+            } else if meta.serialize_with.is_some() || meta.deserialize_with.is_some() {
+                Some("'display_fromstr' fields cannot have (de)serialization functions".to_owned())
+            } else {
+                None
+            };
+
+            if let Some(msg) = conflict {
+                return Err(syn::Error::new(span, msg));
+            }
+        }
+
+        if let Some(span) = format_span {
+            let conflict = if let Some(encoding) = meta.encoding {
+                Some(format!(
+                    "'format' and '{}' cannot be combined",
+                    encoding.name()
+                ))
+            } else if meta.map.is_some() {
+                Some("'format' is not supported on map layout fields".to_owned())
+            } else if meta.serialize_with.is_some() || meta.deserialize_with.is_some() {
+                Some("'format' fields cannot have (de)serialization functions".to_owned())
+            } else {
+                None
+            };
+
+            if let Some(msg) = conflict {
+                return Err(syn::Error::new(span, msg));
+            }
+        }
+
+        if input.ident.is_none() {
+            if meta.borrow
+                || meta.display_fromstr
+                || meta.encoding.is_some()
+                || meta.flatten
+                || meta.format.is_some()
+                || meta.wrapper.is_some()
+                || meta.map.is_some()
+                || meta.min_len.is_some()
+                || meta.max_len.is_some()
+                || meta.pattern.is_some()
+                || occurs_span.is_some()
+                || meta.tag_name
+            {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "tuple struct fields only support 'attribute', 'direct', 'rename', 'ns' and (de)serialization functions",
+                ));
+            } else if meta.tag.is_empty() && (meta.attribute || meta.ns.uri.is_some()) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "tuple struct fields with 'attribute' or 'ns' require 'rename'",
+                ));
+            } else if meta.attribute && meta.direct {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "direct attribute is not supported for attribute fields",
+                ));
             }
         }
 
@@ -181,6 +507,91 @@ impl FieldMeta {
     }
 }
 
+/// Type to deserialize before converting into the container type
+enum Conversion {
+    /// `From<T>`
+    From(syn::Type),
+    /// `TryFrom<T>`, where the error type implements `Display`
+    TryFrom(syn::Type),
+}
+
+fn borrow_only(name: &str, span: Span, options: &[(&str, bool)]) -> Result<(), syn::Error> {
+    match options.iter().find(|(option, set)| *set && *option != name) {
+        Some((option, _)) => Err(syn::Error::new(
+            span,
+            format!("'{name}' can only be combined with 'borrow', not '{option}'"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn usize_value(lit: Literal, name: &str) -> Result<usize, syn::Error> {
+    match lit.to_string().parse() {
+        Ok(value) => Ok(value),
+        Err(_) => Err(syn::Error::new(
+            lit.span(),
+            format!("'{name}' must be a non-negative integer"),
+        )),
+    }
+}
+
+fn conversion_type(lit: &Literal) -> Result<syn::Type, syn::Error> {
+    syn::parse_str(lit.to_string().trim_matches('"')).map_err(|err| {
+        syn::Error::new(
+            lit.span(),
+            format!("failed to parse conversion type: {err}"),
+        )
+    })
+}
+
+/// Alternative text encodings for scalar collection fields
+#[derive(Clone, Copy, Debug)]
+enum Encoding {
+    /// Whitespace-separated list of scalar values
+    List,
+    /// Base64-encoded bytes
+    Base64,
+    /// Hex-encoded bytes
+    Hex,
+}
+
+impl Encoding {
+    fn name(self) -> &'static str {
+        match self {
+            Self::List => "list",
+            Self::Base64 => "base64",
+            Self::Hex => "hex",
+        }
+    }
+
+    /// Path to the library function used to deserialize the field
+    fn deserialize_fn(self) -> TokenStream {
+        match self {
+            Self::List => quote!(::instant_xml::from_xml_list),
+            Self::Base64 => quote!(::instant_xml::from_xml_base64),
+            Self::Hex => quote!(::instant_xml::from_xml_hex),
+        }
+    }
+
+    /// Path to the library type used to serialize a slice of the field
+    fn serialize_ref(self) -> TokenStream {
+        match self {
+            Self::List => quote!(::instant_xml::ListRef),
+            Self::Base64 => quote!(::instant_xml::Base64Ref),
+            Self::Hex => quote!(::instant_xml::HexRef),
+        }
+    }
+}
+
+/// Alternative entry layouts for map fields
+#[derive(Debug)]
+enum MapLayout {
+    /// `<entry key="k">v</entry>`
+    KeyAttribute(Literal),
+    /// `<entry><key>k</key><value>v</value></entry>`
+    Pair { key: Literal, value: Literal },
+}
+
 #[derive(Debug, Default)]
 struct VariantMeta {
     serialize_as: TokenStream,
@@ -254,6 +665,37 @@ impl VariantMeta {
     }
 }
 
+/// The type used to (de)serialize a `display_fromstr` field of the given type
+///
+/// Wraps the type in `DisplayFromStr`, or its inner type for `Option` fields.
+fn display_fromstr_type(ty: &syn::Type) -> syn::Type {
+    match option_inner(ty) {
+        Some(inner) => {
+            syn::parse_quote!(::std::option::Option<::instant_xml::DisplayFromStr<#inner>>)
+        }
+        None => syn::parse_quote!(::instant_xml::DisplayFromStr<#ty>),
+    }
+}
+
+/// If the given type is (syntactically) an `Option<T>`, return `T`
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Group(group) => return option_inner(&group.elem),
+        syn::Type::Path(ty) if ty.qself.is_none() => &ty.path,
+        _ => return None,
+    };
+
+    let seg = path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+
+    match (seg.ident == "Option", args.args.first()) {
+        (true, Some(syn::GenericArgument::Type(inner))) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
 fn discard_lifetimes(
     ty: &mut syn::Type,
     borrowed: &mut BTreeSet<syn::Lifetime>,
@@ -328,6 +770,7 @@ fn discard_path_lifetimes(
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Mode {
+    Choice,
     Forward,
     Scalar,
     Transparent,
@@ -5,7 +5,7 @@ use proc_macro2::{Delimiter, Group, Literal, Punct, Span, TokenStream, TokenTree
 use quote::ToTokens;
 use syn::punctuated::Punctuated;
 
-use super::Mode;
+use super::{Encoding, Mode};
 
 #[derive(Debug, Default)]
 pub(crate) struct NamespaceMeta {
@@ -301,6 +301,21 @@ pub(crate) fn meta_items(attrs: &[syn::Attribute]) -> Vec<(MetaItem, Span)> {
                 } else if id == "direct" {
                     items.push((MetaItem::Direct, span));
                     MetaState::Comma
+                } else if id == "display_fromstr" {
+                    items.push((MetaItem::DisplayFromStr, span));
+                    MetaState::Comma
+                } else if id == "flatten" {
+                    items.push((MetaItem::Flatten, span));
+                    MetaState::Comma
+                } else if id == "list" {
+                    items.push((MetaItem::Encoding(Encoding::List), span));
+                    MetaState::Comma
+                } else if id == "base64" {
+                    items.push((MetaItem::Encoding(Encoding::Base64), span));
+                    MetaState::Comma
+                } else if id == "hex" {
+                    items.push((MetaItem::Encoding(Encoding::Hex), span));
+                    MetaState::Comma
                 } else if id == "transparent" {
                     items.push((MetaItem::Mode(Mode::Transparent), span));
                     MetaState::Comma
@@ -316,13 +331,55 @@ pub(crate) fn meta_items(attrs: &[syn::Attribute]) -> Vec<(MetaItem, Span)> {
                 } else if id == "scalar" {
                     items.push((MetaItem::Mode(Mode::Scalar), span));
                     MetaState::Comma
+                } else if id == "choice" {
+                    items.push((MetaItem::Mode(Mode::Choice), span));
+                    MetaState::Comma
+                } else if id == "with" {
+                    MetaState::With
                 } else if id == "serialize_with" {
                     MetaState::SerializeWith
                 } else if id == "deserialize_with" {
                     MetaState::DeserializeWith
+                } else if id == "format" {
+                    MetaState::Format
+                } else if id == "wrapper" {
+                    MetaState::Wrapper
+                } else if id == "key_attribute" {
+                    MetaState::KeyAttribute
+                } else if id == "key_element" {
+                    MetaState::KeyElement
+                } else if id == "value_element" {
+                    MetaState::ValueElement
+                } else if id == "from" {
+                    MetaState::From
+                } else if id == "try_from" {
+                    MetaState::TryFrom
+                } else if id == "into" {
+                    MetaState::Into
+                } else if id == "validate" {
+                    MetaState::Validate
+                } else if id == "min_len" {
+                    MetaState::MinLen
+                } else if id == "max_len" {
+                    MetaState::MaxLen
+                } else if id == "pattern" {
+                    MetaState::Pattern
+                } else if id == "min_occurs" {
+                    MetaState::MinOccurs
+                } else if id == "max_occurs" {
+                    MetaState::MaxOccurs
                 } else if id == "force_prefix" {
                     items.push((MetaItem::ForcePrefix, span));
                     MetaState::Comma
+                } else if id == "ordered" {
+                    items.push((MetaItem::Ordered, span));
+                    MetaState::Comma
+                } else if id == "any_name" {
+                    items.push((MetaItem::AnyName, span));
+                    MetaState::Comma
+                } else if id == "tag_name" {
+                    items.push((MetaItem::TagName, span));
+                    MetaState::Comma
                 } else {
                     panic!("unexpected key in xml attribute");
                 }
@@ -350,6 +407,13 @@ pub(crate) fn meta_items(attrs: &[syn::Attribute]) -> Vec<(MetaItem, Span)> {
                 items.push((MetaItem::RenameAll(lit), span));
                 MetaState::Comma
             }
+            (MetaState::With, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::WithValue
+            }
+            (MetaState::WithValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::With(lit), span));
+                MetaState::Comma
+            }
             (MetaState::SerializeWith, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
                 MetaState::SerializeWithValue
             }
@@ -364,6 +428,104 @@ pub(crate) fn meta_items(attrs: &[syn::Attribute]) -> Vec<(MetaItem, Span)> {
                 items.push((MetaItem::DeserializeWith(lit), span));
                 MetaState::Comma
             }
+            (MetaState::Format, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::FormatValue
+            }
+            (MetaState::FormatValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::Format(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::Wrapper, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::WrapperValue
+            }
+            (MetaState::WrapperValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::Wrapper(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::KeyAttribute, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::KeyAttributeValue
+            }
+            (MetaState::KeyAttributeValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::KeyAttribute(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::KeyElement, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::KeyElementValue
+            }
+            (MetaState::KeyElementValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::KeyElement(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::ValueElement, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::ValueElementValue
+            }
+            (MetaState::ValueElementValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::ValueElement(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::From, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::FromValue
+            }
+            (MetaState::FromValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::From(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::TryFrom, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::TryFromValue
+            }
+            (MetaState::TryFromValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::TryFrom(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::Into, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::IntoValue
+            }
+            (MetaState::IntoValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::Into(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::Validate, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::ValidateValue
+            }
+            (MetaState::ValidateValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::Validate(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::MinLen, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::MinLenValue
+            }
+            (MetaState::MinLenValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::MinLen(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::MaxLen, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::MaxLenValue
+            }
+            (MetaState::MaxLenValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::MaxLen(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::Pattern, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::PatternValue
+            }
+            (MetaState::PatternValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::Pattern(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::MinOccurs, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::MinOccursValue
+            }
+            (MetaState::MinOccursValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::MinOccurs(lit), span));
+                MetaState::Comma
+            }
+            (MetaState::MaxOccurs, TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                MetaState::MaxOccursValue
+            }
+            (MetaState::MaxOccursValue, TokenTree::Literal(lit)) => {
+                items.push((MetaItem::MaxOccurs(lit), span));
+                MetaState::Comma
+            }
             (state, tree) => {
                 panic!(
                     "invalid state transition while parsing xml attribute ({}, {tree})",
@@ -385,10 +547,40 @@ enum MetaState {
     RenameValue,
     RenameAll,
     RenameAllValue,
+    With,
+    WithValue,
     SerializeWith,
     SerializeWithValue,
     DeserializeWith,
     DeserializeWithValue,
+    Format,
+    FormatValue,
+    Wrapper,
+    WrapperValue,
+    KeyAttribute,
+    KeyAttributeValue,
+    KeyElement,
+    KeyElementValue,
+    ValueElement,
+    ValueElementValue,
+    From,
+    FromValue,
+    TryFrom,
+    TryFromValue,
+    Into,
+    IntoValue,
+    Validate,
+    ValidateValue,
+    MinLen,
+    MinLenValue,
+    MaxLen,
+    MaxLenValue,
+    Pattern,
+    PatternValue,
+    MinOccurs,
+    MinOccursValue,
+    MaxOccurs,
+    MaxOccursValue,
 }
 
 impl MetaState {
@@ -401,10 +593,40 @@ impl MetaState {
             Self::RenameValue => "RenameValue",
             Self::RenameAll => "RenameAll",
             Self::RenameAllValue => "RenameAllValue",
+            Self::With => "With",
+            Self::WithValue => "WithValue",
             Self::SerializeWith => "SerializeWith",
             Self::SerializeWithValue => "SerializeWithValue",
             Self::DeserializeWith => "DeserializeWith",
             Self::DeserializeWithValue => "DeserializeWithValue",
+            Self::Format => "Format",
+            Self::FormatValue => "FormatValue",
+            Self::Wrapper => "Wrapper",
+            Self::WrapperValue => "WrapperValue",
+            Self::KeyAttribute => "KeyAttribute",
+            Self::KeyAttributeValue => "KeyAttributeValue",
+            Self::KeyElement => "KeyElement",
+            Self::KeyElementValue => "KeyElementValue",
+            Self::ValueElement => "ValueElement",
+            Self::ValueElementValue => "ValueElementValue",
+            Self::From => "From",
+            Self::FromValue => "FromValue",
+            Self::TryFrom => "TryFrom",
+            Self::TryFromValue => "TryFromValue",
+            Self::Into => "Into",
+            Self::IntoValue => "IntoValue",
+            Self::Validate => "Validate",
+            Self::ValidateValue => "ValidateValue",
+            Self::MinLen => "MinLen",
+            Self::MinLenValue => "MinLenValue",
+            Self::MaxLen => "MaxLen",
+            Self::MaxLenValue => "MaxLenValue",
+            Self::Pattern => "Pattern",
+            Self::PatternValue => "PatternValue",
+            Self::MinOccurs => "MinOccurs",
+            Self::MinOccursValue => "MinOccursValue",
+            Self::MaxOccurs => "MaxOccurs",
+            Self::MaxOccursValue => "MaxOccursValue",
         }
     }
 }
@@ -490,11 +712,32 @@ pub(crate) enum MetaItem {
     Attribute,
     Borrow,
     Direct,
+    DisplayFromStr,
+    Encoding(Encoding),
+    Flatten,
     Ns(NamespaceMeta),
     Rename(Literal),
     Mode(Mode),
     RenameAll(Literal),
+    With(Literal),
     SerializeWith(Literal),
     DeserializeWith(Literal),
     ForcePrefix,
+    Ordered,
+    AnyName,
+    TagName,
+    Format(Literal),
+    Wrapper(Literal),
+    KeyAttribute(Literal),
+    KeyElement(Literal),
+    ValueElement(Literal),
+    From(Literal),
+    TryFrom(Literal),
+    Into(Literal),
+    Validate(Literal),
+    MinLen(Literal),
+    MaxLen(Literal),
+    Pattern(Literal),
+    MinOccurs(Literal),
+    MaxOccurs(Literal),
 }
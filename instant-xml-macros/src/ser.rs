@@ -1,10 +1,13 @@
 use std::collections::BTreeSet;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
 use syn::spanned::Spanned;
 
-use super::{discard_lifetimes, meta_items, ContainerMeta, FieldMeta, Mode, VariantMeta};
+use super::{
+    discard_lifetimes, meta_items, option_inner, ContainerMeta, FieldMeta, MapLayout, Mode,
+    VariantMeta,
+};
 use crate::{case::RenameRule, Namespace};
 
 pub(crate) fn to_xml(input: &syn::DeriveInput) -> TokenStream {
@@ -13,12 +16,18 @@ pub(crate) fn to_xml(input: &syn::DeriveInput) -> TokenStream {
         Err(e) => return e.to_compile_error(),
     };
 
+    if let Some(ty) = &meta.into {
+        return serialize_into(input, ty);
+    }
+
     match (&input.data, meta.mode) {
         (syn::Data::Struct(data), None) => serialize_struct(input, data, meta),
         (syn::Data::Struct(data), Some(Mode::Transparent)) => {
             serialize_inline_struct(input, data, meta)
         }
+        (syn::Data::Struct(data), Some(Mode::Scalar)) => serialize_scalar_struct(input, data),
         (syn::Data::Enum(data), Some(Mode::Scalar)) => serialize_scalar_enum(input, data, meta),
+        (syn::Data::Enum(data), Some(Mode::Choice)) => serialize_choice_enum(input, data, meta),
         (syn::Data::Enum(data), Some(Mode::Forward)) => serialize_forward_enum(input, data, meta),
         (syn::Data::Struct(_), Some(mode)) => syn::Error::new(
             input.span(),
@@ -86,6 +95,141 @@ fn serialize_scalar_enum(
     )
 }
 
+/// Converts a clone of the value into the intermediate type, which is then serialized
+fn serialize_into(input: &syn::DeriveInput, ty: &syn::Type) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote!(
+        impl #impl_generics ToXml for #ident #ty_generics #where_clause {
+            fn serialize<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                &self,
+                field: Option<::instant_xml::Id<'_>>,
+                serializer: &mut instant_xml::Serializer<W>,
+            ) -> ::std::result::Result<(), instant_xml::Error> {
+                let value: #ty = ::core::convert::Into::into(::core::clone::Clone::clone(self));
+                ::instant_xml::ToXml::serialize(&value, field, serializer)
+            }
+        }
+    )
+}
+
+/// The expression used to serialize the value of a named field
+fn field_value(field: &syn::Field, field_meta: &FieldMeta) -> TokenStream {
+    let field_name = field.ident.as_ref().unwrap();
+    match (field_meta.display_fromstr, option_inner(&field.ty)) {
+        (false, _) => quote!(self.#field_name),
+        (true, Some(_)) => quote!((self.#field_name.as_ref().map(::instant_xml::DisplayFromStr))),
+        (true, None) => quote!((::instant_xml::DisplayFromStr(&self.#field_name))),
+    }
+}
+
+/// Newtype structs delegate to the inner type; other structs are written as an escaped string
+/// using `Display`
+fn serialize_scalar_struct(input: &syn::DeriveInput, data: &syn::DataStruct) -> TokenStream {
+    let mut generics = input.generics.clone();
+    let body = match &data.fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            for param in generics.type_params_mut() {
+                param
+                    .bounds
+                    .push(syn::parse_str("::instant_xml::ToXml").unwrap());
+            }
+
+            quote!(
+                fn serialize<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                    &self,
+                    field: Option<::instant_xml::Id<'_>>,
+                    serializer: &mut instant_xml::Serializer<W>,
+                ) -> ::std::result::Result<(), instant_xml::Error> {
+                    ::instant_xml::ToXml::serialize(&self.0, field, serializer)
+                }
+
+                fn present(&self) -> bool {
+                    ::instant_xml::ToXml::present(&self.0)
+                }
+            )
+        }
+        _ => quote!(
+            fn serialize<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                &self,
+                field: Option<::instant_xml::Id<'_>>,
+                serializer: &mut instant_xml::Serializer<W>,
+            ) -> ::std::result::Result<(), instant_xml::Error> {
+                let value = ::std::string::ToString::to_string(self);
+                ::instant_xml::ToXml::serialize(value.as_str(), field, serializer)
+            }
+        ),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    quote!(
+        impl #impl_generics ToXml for #ident #ty_generics #where_clause {
+            #body
+        }
+    )
+}
+
+fn serialize_choice_enum(
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    meta: ContainerMeta<'_>,
+) -> TokenStream {
+    let ident = &input.ident;
+    let mut variants = TokenStream::new();
+
+    for variant in data.variants.iter() {
+        let meta = match VariantMeta::from_variant(variant, &meta) {
+            Ok(meta) => meta,
+            Err(err) => return err.to_compile_error(),
+        };
+
+        let v_ident = &variant.ident;
+        let serialize_as = meta.serialize_as;
+        variants.extend(quote!(#ident::#v_ident => #serialize_as,));
+    }
+
+    // Without an explicit namespace, variant elements inherit the namespace of the
+    // element they are nested in.
+    let variant_ns = match &meta.ns.uri {
+        Some(ns) => quote!(#ns),
+        None => quote!(match field {
+            Some(id) => id.ns,
+            None => serializer.default_ns(),
+        }),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote!(
+        impl #impl_generics ToXml for #ident #ty_generics #where_clause {
+            fn serialize<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                &self,
+                field: Option<::instant_xml::Id<'_>>,
+                serializer: &mut instant_xml::Serializer<W>,
+            ) -> ::std::result::Result<(), instant_xml::Error> {
+                let element = match field {
+                    Some(id) => {
+                        let element = serializer.write_start(id.name, id.ns, None::<::instant_xml::ser::Context<0>>)?;
+                        serializer.end_start()?;
+                        Some(element)
+                    }
+                    None => None,
+                };
+
+                let ns = #variant_ns;
+                let name = match self { #variants };
+                serializer.write_start(name, ns, None::<::instant_xml::ser::Context<0>>)?;
+                serializer.end_empty()?;
+                if let Some(element) = element {
+                    serializer.write_close(element)?;
+                }
+
+                Ok(())
+            }
+        }
+    )
+}
+
 fn serialize_forward_enum(
     input: &syn::DeriveInput,
     data: &syn::DataEnum,
@@ -122,7 +266,7 @@ fn serialize_forward_enum(
         }
 
         let v_ident = &variant.ident;
-        variants.extend(quote!(#ident::#v_ident(inner) => inner.serialize(None, serializer)?,));
+        variants.extend(quote!(#ident::#v_ident(inner) => inner.serialize(field, serializer)?,));
     }
 
     let default_namespace = meta.default_namespace();
@@ -153,6 +297,12 @@ fn serialize_forward_enum(
                 field: Option<::instant_xml::Id<'_>>,
                 serializer: &mut instant_xml::Serializer<W>,
             ) -> ::std::result::Result<(), instant_xml::Error> {
+                // Only a name for the root element is passed on to the variant
+                let field = match serializer.root_name() {
+                    true => field,
+                    false => None,
+                };
+
                 match self {
                     #variants
                 }
@@ -171,18 +321,61 @@ fn serialize_struct(
     let mut out = StructOutput::default();
     match &data.fields {
         syn::Fields::Named(fields) => {
-            if let Err(err) = out.named_fields(fields, false, &meta) {
+            if let Err(err) = out.named_fields(fields, Layout::Element, &meta) {
                 return err;
             }
         }
         syn::Fields::Unnamed(fields) => {
-            if let Err(err) = out.unnamed_fields(fields, false) {
+            if let Err(err) = out.unnamed_fields(fields, false, &meta) {
                 return err;
             }
         }
         syn::Fields::Unit => out.body.extend(quote!(serializer.end_empty()?;)),
     }
 
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(syn::parse_str("::instant_xml::ToXml").unwrap());
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+
+    // Flattening only merges attributes and child elements into the parent
+    let flatten_impl = match &data.fields {
+        syn::Fields::Named(fields) if !out.direct && out.tag_name.is_none() => {
+            let mut flat = StructOutput::default();
+            if let Err(err) = flat.named_fields(fields, Layout::Flatten, &meta) {
+                return err;
+            }
+
+            let (checks, attributes, children) = (flat.checks, flat.attributes, flat.body);
+            quote!(
+                impl #impl_generics ::instant_xml::ToXmlFlatten for #ident #ty_generics #where_clause {
+                    fn serialize_attributes<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                        &self,
+                        serializer: &mut instant_xml::Serializer<W>,
+                    ) -> ::std::result::Result<(), instant_xml::Error> {
+                        #checks
+                        #attributes
+                        Ok(())
+                    }
+
+                    fn serialize_children<W: ::core::fmt::Write + ?::core::marker::Sized>(
+                        &self,
+                        serializer: &mut instant_xml::Serializer<W>,
+                    ) -> ::std::result::Result<(), instant_xml::Error> {
+                        #children
+                        Ok(())
+                    }
+                }
+            )
+        }
+        _ => TokenStream::new(),
+    };
+
     let default_namespace = meta.default_namespace();
     let force_prefix = meta.force_prefix;
     let cx_len = meta.ns.prefixes.len();
@@ -198,17 +391,26 @@ fn serialize_struct(
         ));
     }
 
-    let mut generics = input.generics.clone();
-    for param in generics.type_params_mut() {
-        param
-            .bounds
-            .push(syn::parse_str("::instant_xml::ToXml").unwrap());
-    }
-
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let tag = meta.tag();
-    let ident = &input.ident;
+    let (tag, ns) = match &out.tag_name {
+        Some(field) => (
+            {
+                let field_str = format!("{ident}::{field}");
+                quote!(::instant_xml::validate::tag_name(
+                    ::instant_xml::TagName::name(&self.#field),
+                    #field_str,
+                )?)
+            },
+            quote!(::instant_xml::TagName::ns(&self.#field).unwrap_or(#default_namespace)),
+        ),
+        None => (meta.tag(), default_namespace),
+    };
 
+    // A name passed in for the root element overrides the container's own
+    let name = quote!(match field {
+        Some(id) if serializer.root_name() => (id.name, id.ns),
+        _ => (#tag, #ns),
+    });
+    let checks = &out.checks;
     quote!(
         impl #impl_generics ToXml for #ident #ty_generics #where_clause {
             fn serialize<W: ::core::fmt::Write + ?::core::marker::Sized>(
@@ -216,15 +418,20 @@ fn serialize_struct(
                 field: Option<::instant_xml::Id<'_>>,
                 serializer: &mut instant_xml::Serializer<W>,
             ) -> ::std::result::Result<(), instant_xml::Error> {
+                #checks
+
                 // Start tag
                 #context
-                let element = serializer.write_start(#tag, #default_namespace, Some(new))?;
+                let (name, ns) = #name;
+                let element = serializer.write_start(name, ns, Some(new))?;
 
                 // Finalize start element
                 #out
                 Ok(())
             }
-        };
+        }
+
+        #flatten_impl
     )
 }
 
@@ -259,12 +466,12 @@ fn serialize_inline_struct(
     let mut out = StructOutput::default();
     match &data.fields {
         syn::Fields::Named(fields) => {
-            if let Err(err) = out.named_fields(fields, true, &meta) {
+            if let Err(err) = out.named_fields(fields, Layout::Inline, &meta) {
                 return err;
             }
         }
         syn::Fields::Unnamed(fields) => {
-            if let Err(err) = out.unnamed_fields(fields, true) {
+            if let Err(err) = out.unnamed_fields(fields, true, &meta) {
                 return err;
             }
         }
@@ -280,6 +487,7 @@ fn serialize_inline_struct(
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ident = &input.ident;
+    let checks = &out.checks;
     quote!(
         impl #impl_generics ToXml for #ident #ty_generics #where_clause {
             fn serialize<W: ::core::fmt::Write + ?::core::marker::Sized>(
@@ -287,6 +495,7 @@ fn serialize_inline_struct(
                 field: Option<::instant_xml::Id<'_>>,
                 serializer: &mut instant_xml::Serializer<W>,
             ) -> ::std::result::Result<(), instant_xml::Error> {
+                #checks
                 #out
                 Ok(())
             }
@@ -296,16 +505,32 @@ fn serialize_inline_struct(
 
 #[derive(Default)]
 struct StructOutput {
+    /// Occurrence checks, run before anything is written
+    checks: TokenStream,
     body: TokenStream,
     attributes: TokenStream,
     borrowed: BTreeSet<syn::Lifetime>,
+    direct: bool,
+    /// The field holding the element's name, if any
+    tag_name: Option<Ident>,
+}
+
+/// How the named fields of a struct relate to its element
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Layout {
+    /// Fields are written as part of the struct's own element
+    Element,
+    /// Child elements are written without a wrapping element (`transparent`)
+    Inline,
+    /// Attributes and child elements are written separately into a parent's element
+    Flatten,
 }
 
 impl StructOutput {
     fn named_fields(
         &mut self,
         fields: &syn::FieldsNamed,
-        inline: bool,
+        layout: Layout,
         meta: &ContainerMeta<'_>,
     ) -> Result<(), TokenStream> {
         let fields = fields
@@ -318,10 +543,34 @@ impl StructOutput {
         let mut attrs_only = true;
         let mut direct = None;
         for (field, field_meta) in &fields {
-            if !field_meta.attribute {
+            if field_meta.tag_name {
+                if layout == Layout::Inline {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "inline structs cannot have tag_name fields",
+                    )
+                    .into_compile_error());
+                } else if self.tag_name.is_some() {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "only one tag_name field is allowed",
+                    )
+                    .into_compile_error());
+                }
+
+                self.tag_name = field.ident.clone();
+            } else if !field_meta.attribute {
                 attrs_only = false;
             }
 
+            if layout == Layout::Inline && field_meta.flatten {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "inline structs cannot have flatten fields",
+                )
+                .into_compile_error());
+            }
+
             if direct.is_some() {
                 return Err(
                     syn::Error::new(field.span(), "direct field must be the last")
@@ -330,15 +579,16 @@ impl StructOutput {
             }
 
             if field_meta.direct {
-                direct = Some(field.ident.as_ref().unwrap());
+                direct = Some(field_value(field, field_meta));
             }
         }
 
-        if !inline {
-            self.body.extend(match (attrs_only, direct) {
+        self.direct = direct.is_some();
+        if layout == Layout::Element {
+            self.body.extend(match (attrs_only, &direct) {
                 (true, _) => quote!(serializer.end_empty()?;),
-                (false, Some(field)) => quote!(
-                    match self.#field.present() {
+                (false, Some(value)) => quote!(
+                    match #value.present() {
                         true => serializer.end_start()?,
                         false => serializer.end_empty()?,
                     }
@@ -348,11 +598,19 @@ impl StructOutput {
         }
 
         for (field, field_meta) in fields {
+            if layout == Layout::Inline && field_meta.wrapper.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "inline structs cannot have wrapper fields",
+                )
+                .into_compile_error());
+            }
+
             if let Err(err) = self.named_field(field, field_meta, meta) {
                 return Err(err.to_compile_error());
             }
 
-            if inline && !self.attributes.is_empty() {
+            if layout == Layout::Inline && !self.attributes.is_empty() {
                 return Err(syn::Error::new(
                     field.span(),
                     "no attributes allowed on inline structs",
@@ -361,10 +619,10 @@ impl StructOutput {
             }
         }
 
-        if !inline && !attrs_only {
+        if layout == Layout::Element && !attrs_only {
             self.body.extend(match direct {
-                Some(field) => quote!(
-                    match self.#field.present() {
+                Some(value) => quote!(
+                    match #value.present() {
                         true => serializer.write_close(element)?,
                         false => (),
                     }
@@ -383,6 +641,55 @@ impl StructOutput {
         meta: &ContainerMeta<'_>,
     ) -> Result<(), syn::Error> {
         let field_name = field.ident.as_ref().unwrap();
+        if field_meta.tag_name {
+            return Ok(());
+        } else if field_meta.flatten {
+            self.attributes.extend(quote!(
+                ::instant_xml::ToXmlFlatten::serialize_attributes(&self.#field_name, serializer)?;
+            ));
+            self.body.extend(quote!(
+                ::instant_xml::ToXmlFlatten::serialize_children(&self.#field_name, serializer)?;
+            ));
+            return Ok(());
+        }
+
+        let field_str = format!("{}::{field_name}", meta.input.ident);
+        if let Some(min) = field_meta.min_occurs {
+            self.checks.extend(quote!(
+                ::instant_xml::validate::min_occurs(&self.#field_name, #min, #field_str)?;
+            ));
+        }
+
+        if let Some(max) = field_meta.max_occurs {
+            self.checks.extend(quote!(
+                ::instant_xml::validate::max_occurs(&self.#field_name, #max, #field_str)?;
+            ));
+        }
+
+        let value = field_value(field, &field_meta);
+        let serialize_with = field_meta
+            .serialize_with
+            .as_ref()
+            .map(|with| {
+                let path = with.to_string();
+                syn::parse_str::<syn::Path>(path.trim_matches('"')).map_err(|err| {
+                    syn::Error::new(
+                        with.span(),
+                        format!("failed to parse serialize_with as path: {err}"),
+                    )
+                })
+            })
+            .transpose()?;
+
+        if let (Some(encoding), Some(_)) = (field_meta.encoding, &serialize_with) {
+            return Err(syn::Error::new(
+                field.span(),
+                format!(
+                    "{} fields cannot have serialization functions",
+                    encoding.name()
+                ),
+            ));
+        }
 
         let tag = field_meta.tag;
         let default_ns = match &meta.ns.uri {
@@ -390,6 +697,22 @@ impl StructOutput {
             None => quote!(""),
         };
 
+        if field_meta.wrapper.is_some()
+            && (field_meta.attribute || field_meta.direct || field_meta.encoding.is_some())
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "wrapper is only supported on element fields",
+            ));
+        } else if field_meta.map.is_some()
+            && (field_meta.attribute || field_meta.direct || field_meta.encoding.is_some())
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "map layouts are only supported on element fields",
+            ));
+        }
+
         if field_meta.attribute {
             if field_meta.direct {
                 return Err(syn::Error::new(
@@ -421,12 +744,42 @@ impl StructOutput {
                 None => (quote!(""), quote!()),
             };
 
-            self.attributes.extend(quote!(
-                #error
-                if self.#field_name.present() {
-                    serializer.write_attr(#tag, #ns, &self.#field_name)?;
-                }
-            ));
+            if let Some(path) = serialize_with {
+                self.attributes.extend(quote!(
+                    #error
+                    serializer.write_attr_with(#tag, #ns, |serializer| {
+                        #path(&self.#field_name, serializer)
+                    })?;
+                ));
+                return Ok(());
+            }
+
+            self.attributes
+                .extend(match (field_meta.encoding, &field_meta.format) {
+                    (Some(encoding), _) => {
+                        let serialize_ref = encoding.serialize_ref();
+                        quote!(
+                            #error
+                            let value = #serialize_ref(&self.#field_name[..]);
+                            if value.present() {
+                                serializer.write_attr(#tag, #ns, &value)?;
+                            }
+                        )
+                    }
+                    (None, Some(format)) => quote!(
+                        #error
+                        if self.#field_name.present() {
+                            let value = ::instant_xml::Formatted(&self.#field_name, #format);
+                            serializer.write_attr(#tag, #ns, &value)?;
+                        }
+                    ),
+                    (None, None) => quote!(
+                        #error
+                        if #value.present() {
+                            serializer.write_attr(#tag, #ns, &#value)?;
+                        }
+                    ),
+                });
             return Ok(());
         }
 
@@ -437,39 +790,101 @@ impl StructOutput {
 
         let mut no_lifetime_type = field.ty.clone();
         discard_lifetimes(&mut no_lifetime_type, &mut self.borrowed, false, true);
-        if let Some(with) = field_meta.serialize_with {
-            if field_meta.direct {
+        let value_type = match field_meta.display_fromstr {
+            true => quote!(_),
+            false => quote!(#no_lifetime_type),
+        };
+
+        if let Some(path) = serialize_with {
+            if field_meta.wrapper.is_some() {
                 return Err(syn::Error::new(
                     field.span(),
-                    "direct serialization is not supported with `serialize_with`",
+                    "wrapper fields cannot have serialization functions",
+                ));
+            } else if field_meta.map.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "map layout fields cannot have serialization functions",
                 ));
             }
 
-            let path = with.to_string();
-            let path = syn::parse_str::<syn::Path>(path.trim_matches('"')).map_err(|err| {
-                syn::Error::new(
-                    with.span(),
-                    format!("failed to parse serialize_with as path: {err}"),
-                )
-            })?;
-
             self.body
                 .extend(quote!(#path(&self.#field_name, serializer)?;));
             return Ok(());
-        } else if field_meta.direct {
+        }
+
+        let serialize_item = match &field_meta.map {
+            Some(MapLayout::KeyAttribute(key)) => quote!(
+                ::instant_xml::map::serialize_keyed_entries(
+                    &self.#field_name,
+                    #key,
+                    ::instant_xml::Id { ns: #ns, name: #tag },
+                    serializer,
+                )?;
+            ),
+            Some(MapLayout::Pair { key, value }) => quote!(
+                ::instant_xml::map::serialize_pair_entries(
+                    &self.#field_name,
+                    #key,
+                    #value,
+                    ::instant_xml::Id { ns: #ns, name: #tag },
+                    serializer,
+                )?;
+            ),
+            None => match &field_meta.format {
+                Some(format) => quote!(
+                    <#no_lifetime_type as ::instant_xml::ToXmlFormat>::serialize_format(
+                        &self.#field_name,
+                        #format,
+                        Some(::instant_xml::Id { ns: #ns, name: #tag }),
+                        serializer,
+                    )?;
+                ),
+                None => quote!(
+                    <#value_type as ToXml>::serialize(
+                        &#value,
+                        Some(::instant_xml::Id { ns: #ns, name: #tag }),
+                        serializer,
+                    )?;
+                ),
+            },
+        };
+
+        if let Some(wrapper) = &field_meta.wrapper {
             self.body.extend(quote!(
-                <#no_lifetime_type as ToXml>::serialize(
-                    &self.#field_name, None, serializer
+                if self.#field_name.present() {
+                    let wrapper = serializer.write_start(#wrapper, #ns, None::<::instant_xml::ser::Context<0>>)?;
+                    serializer.end_start()?;
+                    #serialize_item
+                    serializer.write_close(wrapper)?;
+                }
+            ));
+        } else if field_meta.map.is_some() {
+            self.body.extend(serialize_item);
+        } else if let Some(encoding) = field_meta.encoding {
+            let field = match field_meta.direct {
+                true => quote!(None),
+                false => quote!(Some(::instant_xml::Id { ns: #ns, name: #tag })),
+            };
+
+            let serialize_ref = encoding.serialize_ref();
+            self.body.extend(quote!(
+                #serialize_ref(&self.#field_name[..]).serialize(#field, serializer)?;
+            ));
+        } else if let (true, Some(format)) = (field_meta.direct, &field_meta.format) {
+            self.body.extend(quote!(
+                <#no_lifetime_type as ::instant_xml::ToXmlFormat>::serialize_format(
+                    &self.#field_name, #format, None, serializer
                 )?;
             ));
-        } else {
+        } else if field_meta.direct {
             self.body.extend(quote!(
-                <#no_lifetime_type as ToXml>::serialize(
-                    &self.#field_name,
-                    Some(::instant_xml::Id { ns: #ns, name: #tag }),
-                    serializer,
+                <#value_type as ToXml>::serialize(
+                    &#value, None, serializer
                 )?;
             ));
+        } else {
+            self.body.extend(serialize_item);
         }
 
         Ok(())
@@ -479,38 +894,119 @@ impl StructOutput {
         &mut self,
         fields: &syn::FieldsUnnamed,
         inline: bool,
+        meta: &ContainerMeta<'_>,
     ) -> Result<(), TokenStream> {
+        let fields = fields
+            .unnamed
+            .iter()
+            .map(|field| FieldMeta::from_field(field, meta).map(|meta| (field, meta)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| err.to_compile_error())?;
+
+        let attrs_only = fields.iter().any(|(_, meta)| meta.attribute)
+            && fields.iter().all(|(_, meta)| meta.attribute);
         if !inline {
-            self.body.extend(quote!(serializer.end_start()?;));
+            self.body.extend(match attrs_only {
+                true => quote!(serializer.end_empty()?;),
+                false => quote!(serializer.end_start()?;),
+            });
         }
 
-        for (index, field) in fields.unnamed.iter().enumerate() {
-            if let Err(err) = self.unnamed_field(field, index) {
+        for (index, (field, field_meta)) in fields.into_iter().enumerate() {
+            if inline && field_meta.attribute {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "no attributes allowed on inline structs",
+                )
+                .to_compile_error());
+            }
+
+            if let Err(err) = self.unnamed_field(field, index, field_meta, meta) {
                 return Err(err.to_compile_error());
             }
         }
 
-        if !inline {
+        if !inline && !attrs_only {
             self.body.extend(quote!(serializer.write_close(element)?;));
         }
 
         Ok(())
     }
 
-    fn unnamed_field(&mut self, field: &syn::Field, index: usize) -> Result<(), syn::Error> {
-        if !field.attrs.is_empty() {
-            return Err(syn::Error::new(
-                field.span(),
-                "unnamed fields cannot have attributes",
-            ));
-        }
-
+    fn unnamed_field(
+        &mut self,
+        field: &syn::Field,
+        index: usize,
+        field_meta: FieldMeta,
+        meta: &ContainerMeta<'_>,
+    ) -> Result<(), syn::Error> {
         let mut no_lifetime_type = field.ty.clone();
         discard_lifetimes(&mut no_lifetime_type, &mut self.borrowed, false, true);
         let index = syn::Index::from(index);
-        self.body.extend(quote!(
-            self.#index.serialize(None, serializer)?;
-        ));
+
+        let serialize_with = field_meta
+            .serialize_with
+            .as_ref()
+            .map(|with| {
+                let path = with.to_string();
+                syn::parse_str::<syn::Path>(path.trim_matches('"')).map_err(|err| {
+                    syn::Error::new(
+                        with.span(),
+                        format!("failed to parse serialize_with as path: {err}"),
+                    )
+                })
+            })
+            .transpose()?;
+
+        let tag = field_meta.tag;
+        if field_meta.attribute {
+            let ns = match &field_meta.ns.uri {
+                Some(Namespace::Path(path)) => match path.get_ident() {
+                    Some(prefix) => quote!(#prefix),
+                    None => {
+                        return Err(syn::Error::new(
+                            path.span(),
+                            "attribute namespace must be a prefix identifier",
+                        ))
+                    }
+                },
+                Some(Namespace::Literal(ns)) => {
+                    return Err(syn::Error::new(
+                        ns.span(),
+                        "attribute namespace must be a prefix identifier",
+                    ))
+                }
+                None => quote!(""),
+            };
+
+            self.attributes.extend(match serialize_with {
+                Some(path) => quote!(
+                    serializer.write_attr_with(#tag, #ns, |serializer| {
+                        #path(&self.#index, serializer)
+                    })?;
+                ),
+                None => quote!(
+                    if self.#index.present() {
+                        serializer.write_attr(#tag, #ns, &self.#index)?;
+                    }
+                ),
+            });
+            return Ok(());
+        }
+
+        let ns = match &field_meta.ns.uri {
+            Some(ns) => quote!(#ns),
+            None => meta.default_namespace(),
+        };
+
+        self.body.extend(match (serialize_with, tag.is_empty()) {
+            (Some(path), _) => quote!(#path(&self.#index, serializer)?;),
+            // Renamed fields are wrapped in an element, even for scalar types
+            (None, false) => quote!(
+                self.#index.serialize(Some(::instant_xml::Id { ns: #ns, name: #tag }), serializer)?;
+            ),
+            (None, true) => quote!(self.#index.serialize(None, serializer)?;),
+        });
 
         Ok(())
     }
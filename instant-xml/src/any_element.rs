@@ -156,6 +156,7 @@ where
 
     type Accumulator = Option<Self>;
     const KIND: Kind = Kind::Element;
+    const ANY_NAME: bool = true;
 }
 
 /// An XML attribute with a resolved namespace URI.
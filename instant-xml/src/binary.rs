@@ -0,0 +1,229 @@
+use std::fmt;
+
+use crate::{display_to_xml, Deserializer, Error, Id, Serializer, ToXml};
+
+/// Deserialize base64-encoded content into a `Vec<u8>`
+///
+/// Helper function used for fields with the `#[xml(base64)]` attribute. Whitespace (including
+/// line breaks) between characters is ignored. Empty content cannot be told apart from missing
+/// content, so the caller is responsible for rejecting duplicate elements.
+pub fn from_xml_base64(
+    into: &mut Vec<u8>,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, '_>,
+) -> Result<(), Error> {
+    decode_into(into, field, deserializer, decode_base64, "base64")
+}
+
+/// Deserialize hex-encoded content into a `Vec<u8>`
+///
+/// Helper function used for fields with the `#[xml(hex)]` attribute. As with
+/// [`from_xml_base64()`], the caller is responsible for rejecting duplicate elements.
+pub fn from_xml_hex(
+    into: &mut Vec<u8>,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, '_>,
+) -> Result<(), Error> {
+    decode_into(
+        into,
+        field,
+        deserializer,
+        |value| decode_hex(value.trim_matches(|c| matches!(c, ' ' | '\t' | '\r' | '\n'))),
+        "hex",
+    )
+}
+
+fn decode_into(
+    into: &mut Vec<u8>,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, '_>,
+    decode: impl FnOnce(&str) -> Option<Vec<u8>>,
+    encoding: &str,
+) -> Result<(), Error> {
+    let Some(value) = deserializer.take_str()? else {
+        return Ok(());
+    };
+
+    match decode(&value) {
+        Some(bytes) => {
+            *into = bytes;
+            Ok(())
+        }
+        None => Err(Error::UnexpectedValue(format!(
+            "invalid {encoding} content for {field}"
+        ))),
+    }
+}
+
+/// Serializes bytes as base64
+///
+/// The encoded content is written directly to the serializer's output, without buffering the
+/// entire payload.
+#[derive(Clone, Copy, Debug)]
+pub struct Base64Ref<'a>(pub &'a [u8]);
+
+impl fmt::Display for Base64Ref<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_base64(self.0, f)
+    }
+}
+
+impl ToXml for Base64Ref<'_> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        display_to_xml(self, field, serializer)
+    }
+
+    fn present(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+/// Serializes bytes as upper case hexadecimal digits
+///
+/// The encoded content is written directly to the serializer's output, without buffering the
+/// entire payload.
+#[derive(Clone, Copy, Debug)]
+pub struct HexRef<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_hex(self.0, f)
+    }
+}
+
+impl ToXml for HexRef<'_> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        display_to_xml(self, field, serializer)
+    }
+
+    fn present(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+pub(crate) fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    fn digit(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            b'A'..=b'F' => Some(b - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let bytes = value.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+
+    bytes
+        .chunks_exact(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+pub(crate) fn encode_hex(bytes: &[u8], out: &mut (impl fmt::Write + ?Sized)) -> fmt::Result {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut buf = [0; 64];
+    for chunk in bytes.chunks(buf.len() / 2) {
+        for (i, b) in chunk.iter().enumerate() {
+            buf[i * 2] = DIGITS[usize::from(b >> 4)];
+            buf[i * 2 + 1] = DIGITS[usize::from(b & 0xf)];
+        }
+
+        // Only ASCII digits were written to the buffer
+        out.write_str(std::str::from_utf8(&buf[..chunk.len() * 2]).unwrap())?;
+    }
+
+    Ok(())
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decode standard base64 with padding, ignoring whitespace
+pub(crate) fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    fn sextet(b: u8) -> Option<u32> {
+        match b {
+            b'A'..=b'Z' => Some(u32::from(b - b'A')),
+            b'a'..=b'z' => Some(u32::from(b - b'a') + 26),
+            b'0'..=b'9' => Some(u32::from(b - b'0') + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let chars = value
+        .bytes()
+        .filter(|b| !matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+        .collect::<Vec<_>>();
+    if chars.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(chars.len() / 4 * 3);
+    let quads = chars.chunks_exact(4);
+    let last = quads.len().checked_sub(1);
+    for (i, quad) in quads.enumerate() {
+        let padding = match quad {
+            [_, _, b'=', b'='] => 2,
+            [_, _, _, b'='] => 1,
+            _ => 0,
+        };
+
+        if padding > 0 && Some(i) != last {
+            return None;
+        }
+
+        let mut bits = 0;
+        for &b in &quad[..4 - padding] {
+            bits = bits << 6 | sextet(b)?;
+        }
+
+        match padding {
+            0 => out.extend_from_slice(&[(bits >> 16) as u8, (bits >> 8) as u8, bits as u8]),
+            // Unused trailing bits must be zero for the canonical encoding of the last byte(s)
+            1 if bits & 0b11 == 0 => {
+                out.extend_from_slice(&[(bits >> 10) as u8, (bits >> 2) as u8])
+            }
+            2 if bits & 0b1111 == 0 => out.push((bits >> 4) as u8),
+            _ => return None,
+        }
+    }
+
+    Some(out)
+}
+
+pub(crate) fn encode_base64(bytes: &[u8], out: &mut (impl fmt::Write + ?Sized)) -> fmt::Result {
+    let mut buf = [0; 64];
+    for chunk in bytes.chunks(buf.len() / 4 * 3) {
+        let mut len = 0;
+        for triple in chunk.chunks(3) {
+            let bits = triple
+                .iter()
+                .enumerate()
+                .fold(0u32, |bits, (i, &b)| bits | u32::from(b) << (16 - i * 8));
+            for i in 0..4 {
+                buf[len + i] = match i <= triple.len() {
+                    true => BASE64_ALPHABET[(bits >> (18 - i * 6)) as usize & 0x3f],
+                    false => b'=',
+                };
+            }
+            len += 4;
+        }
+
+        // Only base64 alphabet characters were written to the buffer
+        out.write_str(std::str::from_utf8(&buf[..len]).unwrap())?;
+    }
+
+    Ok(())
+}
@@ -2,13 +2,14 @@
 
 use std::borrow::Cow;
 use std::collections::{BTreeMap, VecDeque};
-use std::ops::{Deref, DerefMut};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Range};
 use std::str::{self, FromStr};
 
 use xmlparser::{ElementEnd, Token, Tokenizer};
 
 use crate::impls::CowStrAccumulator;
-use crate::{Accumulate, Error, FromXml, Id};
+use crate::{Accumulate, AnyAttribute, Error, FromXml, Id};
 
 /// XML deserializer for iterating over nodes in an element
 pub struct Deserializer<'cx, 'xml> {
@@ -23,7 +24,7 @@ impl<'cx, 'xml> Deserializer<'cx, 'xml> {
     ///
     /// Errors if the input is empty or does not start with an opening element.
     pub fn new(input: &'xml str) -> Result<Self, Error> {
-        let mut context = Mut::Owned(Context::new(input));
+        let mut context = Mut::Owned(Context::new(Tokenizer::from(input)));
         let parent = match context.next() {
             Some(result) => match result? {
                 Node::Open(element) => element,
@@ -59,10 +60,36 @@ impl<'cx, 'xml> Deserializer<'cx, 'xml> {
         self
     }
 
+    /// Ignore any content following the root element
+    ///
+    /// By default, only whitespace, comments and processing instructions may follow the root
+    /// element, and anything else is reported as [`Error::TrailingContent`].
+    pub fn allow_trailing_content(mut self) -> Self {
+        self.context.allow_trailing_content = true;
+        self
+    }
+
     /// Deserialize a value of type `T` from the deserializer's XML input
     pub fn deserialize<T: FromXml<'xml>>(&mut self) -> Result<T, Error> {
         let id = self.context.element_id(&self.parent)?;
-        if !T::matches(id, None) {
+        self.deserialize_root(id, T::matches(id, None))
+    }
+
+    /// Deserialize a value of type `T`, expecting a root element with the given name
+    ///
+    /// The name replaces the one `T` would normally match, so the same type can be read from
+    /// differently named or namespaced root elements.
+    pub fn deserialize_as<T: FromXml<'xml>>(&mut self, root: Id<'_>) -> Result<T, Error> {
+        let id = self.context.element_id(&self.parent)?;
+        self.deserialize_root(id, id == root)
+    }
+
+    fn deserialize_root<T: FromXml<'xml>>(
+        &mut self,
+        id: Id<'xml>,
+        matches: bool,
+    ) -> Result<T, Error> {
+        if !matches {
             return Err(Error::UnexpectedValue(match id.ns.is_empty() {
                 true => format!("unexpected root element {:?}", id.name),
                 false => format!(
@@ -74,7 +101,15 @@ impl<'cx, 'xml> Deserializer<'cx, 'xml> {
 
         let mut value = T::Accumulator::default();
         T::deserialize(&mut value, "<root element>", self)?;
-        value.try_done("<root element>")
+        let value = value.try_done("<root element>")?;
+
+        // Only the deserializer owning the context reads the document as a whole
+        if let (Mut::Owned(_), false) = (&self.context, self.context.allow_trailing_content) {
+            self.ignore()?;
+            self.context.finish()?;
+        }
+
+        Ok(value)
     }
 
     /// Skip all remaining nodes in the current element
@@ -97,7 +132,7 @@ impl<'cx, 'xml> Deserializer<'cx, 'xml> {
     where
         'cx: 'a,
     {
-        self.context.records.push_front(node);
+        self.push_back(node);
         Deserializer {
             parent: self.parent,
             level: self.level,
@@ -106,6 +141,11 @@ impl<'cx, 'xml> Deserializer<'cx, 'xml> {
         }
     }
 
+    /// Return a node to the deserializer, so that it is yielded next
+    pub fn push_back(&mut self, node: Node<'xml>) {
+        self.context.records.push_front(node);
+    }
+
     /// Extract a string value from the current node
     ///
     /// Consumes a text node or attribute value, returning the content as a string.
@@ -150,6 +190,114 @@ impl<'cx, 'xml> Deserializer<'cx, 'xml> {
     }
 }
 
+/// Iterator over the consecutive top-level elements of an XML fragment
+///
+/// Created by [`from_str_fragment()`](crate::from_str_fragment). Each element is deserialized
+/// as a root element, while limits apply across the whole input.
+pub struct Fragments<'xml, T> {
+    context: Context<'xml>,
+    done: bool,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'xml, T: FromXml<'xml>> Fragments<'xml, T> {
+    /// Create an iterator over the top-level elements in `input`
+    pub fn new(input: &'xml str) -> Self {
+        Self {
+            context: Context::new(Tokenizer::from_fragment(input, 0..input.len())),
+            done: false,
+            value: PhantomData,
+        }
+    }
+
+    /// Override default limits for the whole input
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.context.limits = limits;
+        self
+    }
+}
+
+impl<'xml, T: FromXml<'xml>> Iterator for Fragments<'xml, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = loop {
+            match self.context.next()? {
+                Ok(Node::Open(element)) => {
+                    let context = Mut::Ref(&mut self.context);
+                    break Deserializer::with_context(element, context).deserialize();
+                }
+                Ok(Node::Text(text)) if text.trim().is_empty() => continue,
+                Ok(node) => {
+                    break Err(Error::UnexpectedNode(format!(
+                        "{node:?} between top-level elements"
+                    )))
+                }
+                Err(err) => break Err(err),
+            }
+        };
+
+        // Stop after the first error, since the input can no longer be followed reliably
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+/// Parse the start tag in `input[range]` of an element whose content is parsed separately
+///
+/// Returns the element's identifier, its attributes and its namespace scope.
+pub(crate) fn open_element<'xml>(
+    input: &'xml str,
+    range: Range<usize>,
+    limits: Limits,
+) -> Result<(Id<'xml>, Vec<AnyAttribute<'xml>>, Level<'xml>), Error> {
+    let mut context = Context::new(Tokenizer::from_fragment(input, range));
+    context.limits = limits;
+    let id = match context.next() {
+        Some(Ok(Node::Open(element))) => context.element_id(&element)?,
+        Some(Err(err)) => return Err(err),
+        _ => return Err(Error::UnexpectedState("expected start tag")),
+    };
+
+    let mut attributes = Vec::new();
+    while let Some(Node::Attribute(attr)) = context.records.pop_front() {
+        let attr_id = context.attribute_id(&attr)?;
+        attributes.push(AnyAttribute {
+            ns: Cow::Borrowed(attr_id.ns),
+            name: Cow::Borrowed(attr_id.name),
+            value: attr.value,
+        });
+    }
+
+    match context.stack.pop() {
+        Some(level) => Ok((id, attributes, level)),
+        None => Err(Error::UnexpectedState("expected start tag")),
+    }
+}
+
+/// Deserialize the element in `input[range]`, within the namespace scope of its `parent`
+pub(crate) fn deserialize_child<'xml, T: FromXml<'xml>>(
+    input: &'xml str,
+    range: Range<usize>,
+    parent: Level<'xml>,
+    limits: Limits,
+) -> Result<T, Error> {
+    let mut context = Context::new(Tokenizer::from_fragment(input, range));
+    context.limits = limits;
+    context.stack.push(parent);
+    let element = match context.next() {
+        Some(Ok(Node::Open(element))) => element,
+        Some(Err(err)) => return Err(err),
+        _ => return Err(Error::UnexpectedState("expected element")),
+    };
+
+    Deserializer::with_context(element, Mut::Ref(&mut context)).deserialize()
+}
+
 impl<'xml> Iterator for Deserializer<'_, 'xml> {
     type Item = Result<Node<'xml>, Error>;
 
@@ -180,18 +328,36 @@ struct Context<'xml> {
     stack: Vec<Level<'xml>>,
     records: VecDeque<Node<'xml>>,
     limits: Limits,
+    allow_trailing_content: bool,
 }
 
 impl<'xml> Context<'xml> {
-    fn new(input: &'xml str) -> Self {
+    fn new(parser: Tokenizer<'xml>) -> Self {
         Self {
-            parser: Tokenizer::from(input),
+            parser,
             stack: Vec::new(),
             records: VecDeque::new(),
             limits: Limits::default(),
+            allow_trailing_content: false,
         }
     }
 
+    /// Check that only whitespace, comments and processing instructions follow the root element
+    fn finish(&mut self) -> Result<(), Error> {
+        for token in self.parser.by_ref() {
+            match token {
+                Ok(Token::Comment { .. } | Token::ProcessingInstruction { .. }) => continue,
+                Ok(token) => {
+                    let pos = self.parser.stream().gen_text_pos_from(token.span().start());
+                    return Err(Error::TrailingContent(pos));
+                }
+                Err(err) => return Err(Error::TrailingContent(err.pos())),
+            }
+        }
+
+        Ok(())
+    }
+
     fn element_id(&self, element: &Element<'xml>) -> Result<Id<'xml>, Error> {
         Ok(Id {
             ns: match (element.default_ns, element.prefix) {
@@ -609,11 +775,11 @@ pub struct Element<'xml> {
 }
 
 #[derive(Debug)]
-struct Level<'xml> {
-    local: &'xml str,
-    prefix: Option<&'xml str>,
-    default_ns: Option<&'xml str>,
-    prefixes: BTreeMap<&'xml str, &'xml str>,
+pub(crate) struct Level<'xml> {
+    pub(crate) local: &'xml str,
+    pub(crate) prefix: Option<&'xml str>,
+    pub(crate) default_ns: Option<&'xml str>,
+    pub(crate) prefixes: BTreeMap<&'xml str, &'xml str>,
 }
 
 /// An XML attribute during deserialization
@@ -0,0 +1,40 @@
+use std::fmt;
+
+use crate::de::Node;
+use crate::{Accumulate, Deserializer, Error, Serializer};
+
+/// Deserialize a struct's fields as part of a parent element
+///
+/// Implemented by the `FromXml` derive macro for structs with named fields and no `direct`
+/// field. Used for fields with the `#[xml(flatten)]` attribute: the parent offers every attribute
+/// and child element it doesn't handle itself to the flattened field.
+pub trait FromXmlFlatten<'xml>: Sized {
+    /// Deserialize the given attribute or element node into the accumulator
+    ///
+    /// Returns the node if it doesn't belong to any of the flattened fields.
+    fn deserialize_node<'cx>(
+        into: &mut Self::Accumulator,
+        node: Node<'xml>,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<Option<Node<'xml>>, Error>;
+
+    /// The accumulator type used during deserialization
+    type Accumulator: Accumulate<Self>;
+}
+
+/// Serialize a struct's fields as part of a parent element
+///
+/// Implemented by the `ToXml` derive macro for structs with named fields and no `direct` field.
+pub trait ToXmlFlatten {
+    /// Write the attributes for the flattened fields
+    fn serialize_attributes<W: fmt::Write + ?Sized>(
+        &self,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error>;
+
+    /// Write the child elements for the flattened fields
+    fn serialize_children<W: fmt::Write + ?Sized>(
+        &self,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error>;
+}
@@ -0,0 +1,253 @@
+use std::fmt;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+#[cfg(feature = "jiff")]
+use jiff::fmt::strtime;
+#[cfg(feature = "jiff")]
+use jiff::{civil, Timestamp, Zoned};
+#[cfg(feature = "time")]
+use time::{format_description, OffsetDateTime, PrimitiveDateTime};
+
+use crate::{Deserializer, Error, FromXml, Id, Serializer, ToXml};
+
+/// Deserialize a value using a format string
+///
+/// Implemented by types that support the `#[xml(format = "...")]` field attribute. The meaning
+/// of the format string is up to the implementation: `chrono` and `jiff` types use
+/// `strftime`-style formats, while `time` types use that crate's format description syntax
+/// (`[year]-[month]-[day]`).
+pub trait FromXmlFormat<'xml>: FromXml<'xml> {
+    /// Deserialize the text content into the accumulator, parsing it with the given format
+    fn deserialize_format(
+        into: &mut Self::Accumulator,
+        format: &str,
+        field: &'static str,
+        deserializer: &mut Deserializer<'_, 'xml>,
+    ) -> Result<(), Error>;
+}
+
+/// Serialize a value using a format string
+///
+/// Counterpart to [`FromXmlFormat`], used for fields with the `#[xml(format = "...")]`
+/// attribute.
+pub trait ToXmlFormat {
+    /// Serialize the value formatted with the given format, as an element named `field` or as
+    /// plain text content if `field` is `None`
+    fn serialize_format<W: fmt::Write + ?Sized>(
+        &self,
+        format: &str,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error>;
+}
+
+/// Serializes a value with the given format string
+#[derive(Clone, Copy, Debug)]
+pub struct Formatted<'a, T: ?Sized>(pub &'a T, pub &'a str);
+
+impl<T: ToXmlFormat + ToXml + ?Sized> ToXml for Formatted<'_, T> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        self.0.serialize_format(self.1, field, serializer)
+    }
+
+    fn present(&self) -> bool {
+        self.0.present()
+    }
+}
+
+impl<'xml, T: FromXmlFormat<'xml>> FromXmlFormat<'xml> for Option<T> {
+    fn deserialize_format(
+        into: &mut Self::Accumulator,
+        format: &str,
+        field: &'static str,
+        deserializer: &mut Deserializer<'_, 'xml>,
+    ) -> Result<(), Error> {
+        T::deserialize_format(into.get_mut(), format, field, deserializer)
+    }
+}
+
+impl<T: ToXmlFormat> ToXmlFormat for Option<T> {
+    fn serialize_format<W: fmt::Write + ?Sized>(
+        &self,
+        format: &str,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        match self {
+            Some(value) => value.serialize_format(format, field, serializer),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Implements `FromXmlFormat` and `ToXmlFormat` for a date/time type
+///
+/// `$parse` evaluates to an `Option<Self>` given the text content and format string; `$display`
+/// evaluates to a `Result` with a `Display` value to write, which is escaped like a string.
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+macro_rules! xml_format {
+    ($ty:ty, |$value:ident, $fmt:ident| $parse:expr, |$this:ident, $fmt2:ident| $display:expr) => {
+        impl<'xml> FromXmlFormat<'xml> for $ty {
+            fn deserialize_format(
+                into: &mut Self::Accumulator,
+                format: &str,
+                field: &'static str,
+                deserializer: &mut Deserializer<'_, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let Some(value) = deserializer.take_str()? else {
+                    return Ok(());
+                };
+
+                let parse = |$value: &str, $fmt: &str| -> Option<Self> { $parse };
+                match parse(&value, format) {
+                    Some(value) => {
+                        *into = Some(value);
+                        Ok(())
+                    }
+                    None => Err(Error::UnexpectedValue(format!(
+                        "unable to parse `{value}` with format `{format}` for {field}"
+                    ))),
+                }
+            }
+        }
+
+        impl ToXmlFormat for $ty {
+            fn serialize_format<W: fmt::Write + ?Sized>(
+                &self,
+                format: &str,
+                field: Option<Id<'_>>,
+                serializer: &mut Serializer<'_, W>,
+            ) -> Result<(), Error> {
+                let ($this, $fmt2) = (self, format);
+                $display?.to_string().serialize(field, serializer)
+            }
+        }
+    };
+}
+
+// Formats without an offset are interpreted in the target timezone
+#[cfg(feature = "chrono")]
+xml_format!(
+    DateTime<Utc>,
+    |value, format| match DateTime::parse_from_str(value, format) {
+        Ok(dt) => Some(dt.with_timezone(&Utc)),
+        Err(_) => Some(Utc.from_utc_datetime(&NaiveDateTime::parse_from_str(value, format).ok()?)),
+    },
+    |this, format| Ok::<_, Error>(this.format(format))
+);
+#[cfg(feature = "chrono")]
+xml_format!(
+    DateTime<Local>,
+    |value, format| match DateTime::parse_from_str(value, format) {
+        Ok(dt) => Some(dt.with_timezone(&Local)),
+        Err(_) => Local
+            .from_local_datetime(&NaiveDateTime::parse_from_str(value, format).ok()?)
+            .single(),
+    },
+    |this, format| Ok::<_, Error>(this.format(format))
+);
+#[cfg(feature = "chrono")]
+xml_format!(
+    DateTime<FixedOffset>,
+    |value, format| DateTime::parse_from_str(value, format).ok(),
+    |this, format| Ok::<_, Error>(this.format(format))
+);
+#[cfg(feature = "chrono")]
+xml_format!(
+    NaiveDateTime,
+    |value, format| Self::parse_from_str(value, format).ok(),
+    |this, format| Ok::<_, Error>(this.format(format))
+);
+#[cfg(feature = "chrono")]
+xml_format!(
+    NaiveDate,
+    |value, format| Self::parse_from_str(value, format).ok(),
+    |this, format| Ok::<_, Error>(this.format(format))
+);
+#[cfg(feature = "chrono")]
+xml_format!(
+    NaiveTime,
+    |value, format| Self::parse_from_str(value, format).ok(),
+    |this, format| Ok::<_, Error>(this.format(format))
+);
+
+#[cfg(feature = "time")]
+xml_format!(
+    OffsetDateTime,
+    |value, format| Self::parse(value, &time_items(format).ok()?).ok(),
+    |this, format| format_time(format, |items| this.format(items))
+);
+#[cfg(feature = "time")]
+xml_format!(
+    PrimitiveDateTime,
+    |value, format| Self::parse(value, &time_items(format).ok()?).ok(),
+    |this, format| format_time(format, |items| this.format(items))
+);
+#[cfg(feature = "time")]
+xml_format!(
+    time::Date,
+    |value, format| Self::parse(value, &time_items(format).ok()?).ok(),
+    |this, format| format_time(format, |items| this.format(items))
+);
+#[cfg(feature = "time")]
+xml_format!(
+    time::Time,
+    |value, format| Self::parse(value, &time_items(format).ok()?).ok(),
+    |this, format| format_time(format, |items| this.format(items))
+);
+
+#[cfg(feature = "time")]
+fn format_time(
+    format: &str,
+    write: impl FnOnce(
+        &[format_description::BorrowedFormatItem<'_>],
+    ) -> Result<String, time::error::Format>,
+) -> Result<String, Error> {
+    write(&time_items(format)?).map_err(|err| Error::Other(err.to_string()))
+}
+
+#[cfg(feature = "time")]
+fn time_items(format: &str) -> Result<Vec<format_description::BorrowedFormatItem<'_>>, Error> {
+    format_description::parse_borrowed::<1>(format)
+        .map_err(|err| Error::Other(format!("invalid format `{format}`: {err}")))
+}
+
+#[cfg(feature = "jiff")]
+xml_format!(
+    Timestamp,
+    |value, format| strtime::parse(format, value).ok()?.to_timestamp().ok(),
+    |this, format| Ok::<_, Error>(this.strftime(format))
+);
+#[cfg(feature = "jiff")]
+xml_format!(
+    Zoned,
+    |value, format| strtime::parse(format, value).ok()?.to_zoned().ok(),
+    |this, format| Ok::<_, Error>(this.strftime(format))
+);
+#[cfg(feature = "jiff")]
+xml_format!(
+    civil::DateTime,
+    |value, format| Self::strptime(format, value).ok(),
+    |this, format| Ok::<_, Error>(this.strftime(format))
+);
+#[cfg(feature = "jiff")]
+xml_format!(
+    civil::Date,
+    |value, format| Self::strptime(format, value).ok(),
+    |this, format| Ok::<_, Error>(this.strftime(format))
+);
+#[cfg(feature = "jiff")]
+xml_format!(
+    civil::Time,
+    |value, format| Self::strptime(format, value).ok(),
+    |this, format| Ok::<_, Error>(this.strftime(format))
+);
@@ -1,14 +1,41 @@
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::fmt;
-use std::net::IpAddr;
+use std::hash::{BuildHasher, Hash};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::rc::Rc;
 use std::str;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::{any::type_name, marker::PhantomData};
 
 #[cfg(feature = "chrono")]
-use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+#[cfg(feature = "ipnet")]
+use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+#[cfg(feature = "jiff")]
+use jiff::{civil, tz, Timestamp, Zoned};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
+#[cfg(feature = "time")]
+use time::format_description::well_known::Rfc3339;
+#[cfg(feature = "time")]
+use time::macros::format_description;
+#[cfg(feature = "time")]
+use time::{OffsetDateTime, PrimitiveDateTime};
+#[cfg(feature = "url")]
+use url::Url;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
 
 use crate::ser::Context;
+use crate::validate::Occurs;
 use crate::{Accumulate, Deserializer, Error, FromXml, Id, Kind, Serializer, ToXml};
 
 // Deserializer
@@ -81,6 +108,61 @@ impl<'xml, T: FromStr> FromXml<'xml> for FromXmlStr<T> {
     const KIND: Kind = Kind::Scalar;
 }
 
+/// Adapts a type implementing `Display` and `FromStr` for use as a scalar value
+///
+/// Used by the derive macros for fields with the `#[xml(display_fromstr)]` attribute.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DisplayFromStr<T>(pub T);
+
+impl<T> DisplayFromStr<T> {
+    /// Unwrap the inner value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'xml, T: FromStr> FromXml<'xml> for DisplayFromStr<T> {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'_, 'xml>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        let mut value = None;
+        FromXmlStr::<T>::deserialize(&mut value, field, deserializer)?;
+        if let Some(value) = value {
+            *into = Some(Self(value.0));
+        }
+
+        Ok(())
+    }
+
+    type Accumulator = Option<Self>;
+    const KIND: Kind = Kind::Scalar;
+}
+
+// The output may contain markup characters, so it is escaped like a string
+impl<T: fmt::Display> ToXml for DisplayFromStr<T> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        self.0.to_string().serialize(field, serializer)
+    }
+}
+
 impl<'xml> FromXml<'xml> for bool {
     #[inline]
     fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
@@ -224,14 +306,123 @@ from_xml_for_number!(i8);
 from_xml_for_number!(i16);
 from_xml_for_number!(i32);
 from_xml_for_number!(i64);
+from_xml_for_number!(i128);
 from_xml_for_number!(isize);
 from_xml_for_number!(u8);
 from_xml_for_number!(u16);
 from_xml_for_number!(u32);
 from_xml_for_number!(u64);
+from_xml_for_number!(u128);
 from_xml_for_number!(usize);
-from_xml_for_number!(f32);
-from_xml_for_number!(f64);
+from_xml_for_number!(NonZeroI8);
+from_xml_for_number!(NonZeroI16);
+from_xml_for_number!(NonZeroI32);
+from_xml_for_number!(NonZeroI64);
+from_xml_for_number!(NonZeroI128);
+from_xml_for_number!(NonZeroIsize);
+from_xml_for_number!(NonZeroU8);
+from_xml_for_number!(NonZeroU16);
+from_xml_for_number!(NonZeroU32);
+from_xml_for_number!(NonZeroU64);
+from_xml_for_number!(NonZeroU128);
+from_xml_for_number!(NonZeroUsize);
+
+macro_rules! xml_for_float {
+    ($typ:ty) => {
+        impl fmt::Display for XsdFloat<$typ> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let value = self.0;
+                if value.is_nan() {
+                    return f.write_str("NaN");
+                } else if value.is_infinite() {
+                    return f.write_str(if value > 0.0 { "INF" } else { "-INF" });
+                }
+
+                // The shortest round-tripping mantissa, with at least one fractional digit
+                let formatted = format!("{value:E}");
+                match formatted.split_once('E') {
+                    Some((mantissa, exp)) if !mantissa.contains('.') => {
+                        write!(f, "{mantissa}.0E{exp}")
+                    }
+                    _ => f.write_str(&formatted),
+                }
+            }
+        }
+
+        impl ToXml for $typ {
+            fn serialize<W: fmt::Write + ?Sized>(
+                &self,
+                field: Option<Id<'_>>,
+                serializer: &mut Serializer<'_, W>,
+            ) -> Result<(), Error> {
+                DisplayToXml(&XsdFloat(*self)).serialize(field, serializer)
+            }
+        }
+
+        impl<'xml> FromXml<'xml> for $typ {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let Some(value) = deserializer.take_str()? else {
+                    return Ok(());
+                };
+
+                // Only accept the XSD lexical space: Rust also parses forms like `inf` or `infinity`
+                let trimmed = value.as_ref().trim();
+                let parsed = match trimmed {
+                    "INF" | "+INF" => Some(<$typ>::INFINITY),
+                    "-INF" => Some(<$typ>::NEG_INFINITY),
+                    "NaN" => Some(<$typ>::NAN),
+                    _ if trimmed
+                        .bytes()
+                        .all(|b| matches!(b, b'0'..=b'9' | b'+' | b'-' | b'.' | b'e' | b'E')) =>
+                    {
+                        <$typ>::from_str(trimmed).ok()
+                    }
+                    _ => None,
+                };
+
+                match parsed {
+                    Some(value) => *into = Some(value),
+                    None => {
+                        return Err(Error::UnexpectedValue(format!(
+                            "unable to parse number {} from `{value}` for {field}",
+                            type_name::<$typ>()
+                        )))
+                    }
+                }
+
+                Ok(())
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = Kind::Scalar;
+        }
+    };
+}
+
+/// Formats floats using the XSD canonical representation
+///
+/// Finite values are written as a decimal mantissa with a single non-zero digit before the
+/// point (`1.5E0`, `1.0E-7`, `0.0E0`), special values as `INF`, `-INF` and `NaN`.
+struct XsdFloat<T>(T);
+
+xml_for_float!(f32);
+xml_for_float!(f64);
 
 impl<'xml> FromXml<'xml> for char {
     #[inline]
@@ -366,6 +557,7 @@ where
 
     type Accumulator = Vec<T>;
     const KIND: Kind = Kind::Scalar;
+    const ANY_NAME: bool = T::ANY_NAME;
 }
 
 impl<T: ToXml> ToXml for Cow<'_, [T]>
@@ -398,6 +590,7 @@ impl<'xml, T: FromXml<'xml>> FromXml<'xml> for Option<T> {
 
     type Accumulator = OptionAccumulator<T, T::Accumulator>;
     const KIND: Kind = <T>::KIND;
+    const ANY_NAME: bool = T::ANY_NAME;
 }
 
 /// Accumulator for deserializing `Option<T>` values
@@ -422,6 +615,12 @@ impl<T, A: Accumulate<T>> Default for OptionAccumulator<T, A> {
     }
 }
 
+impl<T, A: Accumulate<T> + Occurs> Occurs for OptionAccumulator<T, A> {
+    fn count(&self) -> Option<usize> {
+        self.value.count()
+    }
+}
+
 impl<T, A: Accumulate<T>> Accumulate<Option<T>> for OptionAccumulator<T, A> {
     fn try_done(self, field: &'static str) -> Result<Option<T>, Error> {
         match self.value.try_done(field) {
@@ -435,14 +634,26 @@ to_xml_for_number!(i8);
 to_xml_for_number!(i16);
 to_xml_for_number!(i32);
 to_xml_for_number!(i64);
+to_xml_for_number!(i128);
 to_xml_for_number!(isize);
 to_xml_for_number!(u8);
 to_xml_for_number!(u16);
 to_xml_for_number!(u32);
 to_xml_for_number!(u64);
+to_xml_for_number!(u128);
 to_xml_for_number!(usize);
-to_xml_for_number!(f32);
-to_xml_for_number!(f64);
+to_xml_for_number!(NonZeroI8);
+to_xml_for_number!(NonZeroI16);
+to_xml_for_number!(NonZeroI32);
+to_xml_for_number!(NonZeroI64);
+to_xml_for_number!(NonZeroI128);
+to_xml_for_number!(NonZeroIsize);
+to_xml_for_number!(NonZeroU8);
+to_xml_for_number!(NonZeroU16);
+to_xml_for_number!(NonZeroU32);
+to_xml_for_number!(NonZeroU64);
+to_xml_for_number!(NonZeroU128);
+to_xml_for_number!(NonZeroUsize);
 
 impl ToXml for bool {
     fn serialize<W: fmt::Write + ?Sized>(
@@ -517,43 +728,135 @@ impl<T: ToXml> ToXml for Option<T> {
     }
 }
 
-impl<T: ToXml + ?Sized> ToXml for Box<T> {
+macro_rules! xml_for_pointer {
+    ($ty:ident) => {
+        impl<T: ToXml + ?Sized> ToXml for $ty<T> {
+            fn serialize<W: fmt::Write + ?Sized>(
+                &self,
+                field: Option<Id<'_>>,
+                serializer: &mut Serializer<'_, W>,
+            ) -> Result<(), Error> {
+                self.as_ref().serialize(field, serializer)
+            }
+
+            fn present(&self) -> bool {
+                self.as_ref().present()
+            }
+        }
+
+        from_xml_for_wrapper!($ty);
+    };
+}
+
+macro_rules! from_xml_for_wrapper {
+    ($ty:ident) => {
+        impl<'xml, T: FromXml<'xml>> FromXml<'xml> for $ty<T> {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                T::matches(id, field)
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let mut value = T::Accumulator::default();
+                T::deserialize(&mut value, field, deserializer)?;
+                *into = Some(Self::new(value.try_done(field)?));
+
+                Ok(())
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = T::KIND;
+            const ANY_NAME: bool = T::ANY_NAME;
+        }
+    };
+}
+
+macro_rules! from_xml_for_str_pointer {
+    ($ty:ident) => {
+        impl<'xml> FromXml<'xml> for $ty<str> {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                *into = Some(match deserializer.take_str()? {
+                    Some(value) => Self::from(value.as_ref()),
+                    None => Self::from(""),
+                });
+
+                Ok(())
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = Kind::Scalar;
+        }
+    };
+}
+
+xml_for_pointer!(Box);
+xml_for_pointer!(Rc);
+xml_for_pointer!(Arc);
+from_xml_for_str_pointer!(Box);
+from_xml_for_str_pointer!(Rc);
+from_xml_for_str_pointer!(Arc);
+from_xml_for_wrapper!(Cell);
+from_xml_for_wrapper!(RefCell);
+
+impl<T: ToXml + Copy> ToXml for Cell<T> {
     fn serialize<W: fmt::Write + ?Sized>(
         &self,
         field: Option<Id<'_>>,
         serializer: &mut Serializer<'_, W>,
     ) -> Result<(), Error> {
-        self.as_ref().serialize(field, serializer)
+        self.get().serialize(field, serializer)
     }
-}
 
-impl<'xml, T: FromXml<'xml>> FromXml<'xml> for Box<T> {
-    #[inline]
-    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
-        T::matches(id, field)
+    fn present(&self) -> bool {
+        self.get().present()
     }
+}
 
-    fn deserialize<'cx>(
-        into: &mut Self::Accumulator,
-        field: &'static str,
-        deserializer: &mut Deserializer<'cx, 'xml>,
+impl<T: ToXml + ?Sized> ToXml for RefCell<T> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
     ) -> Result<(), Error> {
-        if into.is_some() {
-            return Err(Error::DuplicateValue(field));
+        match self.try_borrow() {
+            Ok(value) => value.serialize(field, serializer),
+            Err(_) => Err(Error::UnexpectedState("value is mutably borrowed")),
         }
-
-        let mut value = T::Accumulator::default();
-        T::deserialize(&mut value, field, deserializer)?;
-        *into = Some(Self::new(value.try_done(field)?));
-
-        Ok(())
     }
 
-    type Accumulator = Option<Self>;
-    const KIND: Kind = T::KIND;
+    fn present(&self) -> bool {
+        match self.try_borrow() {
+            Ok(value) => value.present(),
+            Err(_) => true,
+        }
+    }
 }
 
-fn encode(input: &str) -> Result<Cow<'_, str>, Error> {
+pub(crate) fn encode(input: &str) -> Result<Cow<'_, str>, Error> {
     let mut result = String::with_capacity(input.len());
     let mut last_end = 0;
     for (start, c) in input.char_indices() {
@@ -597,6 +900,7 @@ impl<'xml, T: FromXml<'xml>> FromXml<'xml> for Vec<T> {
 
     type Accumulator = Self;
     const KIND: Kind = T::KIND;
+    const ANY_NAME: bool = T::ANY_NAME;
 }
 
 impl<T: ToXml> ToXml for Vec<T> {
@@ -623,39 +927,46 @@ impl<T: ToXml> ToXml for [T] {
     }
 }
 
-#[cfg(feature = "chrono")]
-impl ToXml for DateTime<Utc> {
+impl<'xml, T: FromXml<'xml>> FromXml<'xml> for VecDeque<T> {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        T::matches(id, field)
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        let mut value = T::Accumulator::default();
+        T::deserialize(&mut value, field, deserializer)?;
+        into.push_back(value.try_done(field)?);
+        Ok(())
+    }
+
+    type Accumulator = Self;
+    const KIND: Kind = T::KIND;
+    const ANY_NAME: bool = T::ANY_NAME;
+}
+
+impl<T: ToXml> ToXml for VecDeque<T> {
     fn serialize<W: fmt::Write + ?Sized>(
         &self,
         field: Option<Id<'_>>,
         serializer: &mut Serializer<'_, W>,
     ) -> Result<(), Error> {
-        let element = match field {
-            Some(id) => {
-                let element = serializer.write_start(id.name, id.ns, None::<Context<0>>)?;
-                serializer.end_start()?;
-                Some(element)
-            }
-            None => None,
-        };
-
-        serializer.write_str(&self.to_rfc3339())?;
-        if let Some(element) = element {
-            serializer.write_close(element)?;
+        for i in self {
+            i.serialize(field, serializer)?;
         }
 
         Ok(())
     }
 }
 
-#[cfg(feature = "chrono")]
-impl<'xml> FromXml<'xml> for DateTime<Utc> {
+impl<'xml, T: FromXml<'xml> + Ord> FromXml<'xml> for BTreeSet<T> {
     #[inline]
     fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
-        match field {
-            Some(field) => id == field,
-            None => false,
-        }
+        T::matches(id, field)
     }
 
     fn deserialize<'cx>(
@@ -663,28 +974,276 @@ impl<'xml> FromXml<'xml> for DateTime<Utc> {
         field: &'static str,
         deserializer: &mut Deserializer<'cx, 'xml>,
     ) -> Result<(), Error> {
-        if into.is_some() {
-            return Err(Error::DuplicateValue(field));
-        }
-
-        let Some(value) = deserializer.take_str()? else {
-            return Ok(());
-        };
-
-        match DateTime::parse_from_rfc3339(value.as_ref()) {
-            Ok(dt) if dt.timezone().utc_minus_local() == 0 => {
-                *into = Some(dt.with_timezone(&Utc));
-                Ok(())
-            }
-            _ => Err(Error::Other("invalid date/time".into())),
+        let mut value = T::Accumulator::default();
+        T::deserialize(&mut value, field, deserializer)?;
+        match into.insert(value.try_done(field)?) {
+            true => Ok(()),
+            false => Err(Error::DuplicateValue(field)),
         }
     }
 
-    type Accumulator = Option<Self>;
-    const KIND: Kind = Kind::Scalar;
+    type Accumulator = Self;
+    const KIND: Kind = T::KIND;
+    const ANY_NAME: bool = T::ANY_NAME;
 }
 
-#[cfg(feature = "chrono")]
+impl<T: ToXml> ToXml for BTreeSet<T> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        for i in self {
+            i.serialize(field, serializer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'xml, T, S> FromXml<'xml> for HashSet<T, S>
+where
+    T: FromXml<'xml> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        T::matches(id, field)
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        let mut value = T::Accumulator::default();
+        T::deserialize(&mut value, field, deserializer)?;
+        match into.insert(value.try_done(field)?) {
+            true => Ok(()),
+            false => Err(Error::DuplicateValue(field)),
+        }
+    }
+
+    type Accumulator = Self;
+    const KIND: Kind = T::KIND;
+    const ANY_NAME: bool = T::ANY_NAME;
+}
+
+impl<T: ToXml, S> ToXml for HashSet<T, S> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        for i in self {
+            i.serialize(field, serializer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'xml, T: FromXml<'xml>, const N: usize> FromXml<'xml> for [T; N] {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        T::matches(id, field)
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        if into.len() == N {
+            return Err(Error::TooManyOccurrences {
+                field,
+                count: N + 1,
+                max: N,
+            });
+        }
+
+        let mut value = T::Accumulator::default();
+        T::deserialize(&mut value, field, deserializer)?;
+        into.push(value.try_done(field)?);
+        Ok(())
+    }
+
+    type Accumulator = Vec<T>;
+    const KIND: Kind = T::KIND;
+    const ANY_NAME: bool = T::ANY_NAME;
+}
+
+impl<T: ToXml, const N: usize> ToXml for [T; N] {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        self.as_slice().serialize(field, serializer)
+    }
+}
+
+/// Accumulator for deserializing tuples, filled in element order
+pub struct TupleAccumulator<T> {
+    position: usize,
+    values: T,
+}
+
+impl<T: Default> Default for TupleAccumulator<T> {
+    fn default() -> Self {
+        Self {
+            position: 0,
+            values: T::default(),
+        }
+    }
+}
+
+/// Deserialize the next element of a tuple into the accumulator for its position
+fn deserialize_tuple_element<'xml, T: FromXml<'xml>>(
+    into: &mut T::Accumulator,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, 'xml>,
+) -> Result<(), Error> {
+    match T::KIND {
+        Kind::Element => {
+            let id = deserializer.parent();
+            if !T::matches(id, None) {
+                return Err(Error::UnexpectedTag(format!("{} for {field}", id.name)));
+            }
+
+            T::deserialize(into, field, deserializer)
+        }
+        Kind::Scalar => {
+            T::deserialize(into, field, deserializer)?;
+            deserializer.ignore()
+        }
+    }
+}
+
+macro_rules! tuple_impls {
+    ($(($($n:tt $name:ident $acc:ident),+))+) => {
+        $(
+            impl<'xml, $($name: FromXml<'xml>),+> FromXml<'xml> for ($($name,)+) {
+                #[inline]
+                fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                    $($name::matches(id, field))||+
+                }
+
+                fn deserialize<'cx>(
+                    into: &mut Self::Accumulator,
+                    field: &'static str,
+                    deserializer: &mut Deserializer<'cx, 'xml>,
+                ) -> Result<(), Error> {
+                    let position = into.position;
+                    into.position += 1;
+                    match position {
+                        $($n => deserialize_tuple_element::<$name>(&mut into.values.$n, field, deserializer),)+
+                        _ => Err(Error::DuplicateValue(field)),
+                    }
+                }
+
+                type Accumulator = TupleAccumulator<($($name::Accumulator,)+)>;
+                const KIND: Kind = Kind::Element;
+                const ANY_NAME: bool = $($name::ANY_NAME)||+;
+            }
+
+            impl<$($name, $acc: Accumulate<$name>),+> Accumulate<($($name,)+)> for TupleAccumulator<($($acc,)+)> {
+                fn try_done(self, field: &'static str) -> Result<($($name,)+), Error> {
+                    Ok(($(self.values.$n.try_done(field)?,)+))
+                }
+            }
+
+            impl<$($name: ToXml),+> ToXml for ($($name,)+) {
+                fn serialize<W: fmt::Write + ?Sized>(
+                    &self,
+                    field: Option<Id<'_>>,
+                    serializer: &mut Serializer<'_, W>,
+                ) -> Result<(), Error> {
+                    $(self.$n.serialize(field, serializer)?;)+
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+tuple_impls! {
+    (0 T0 A0)
+    (0 T0 A0, 1 T1 A1)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5, 6 T6 A6)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5, 6 T6 A6, 7 T7 A7)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5, 6 T6 A6, 7 T7 A7, 8 T8 A8)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5, 6 T6 A6, 7 T7 A7, 8 T8 A8, 9 T9 A9)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5, 6 T6 A6, 7 T7 A7, 8 T8 A8, 9 T9 A9, 10 T10 A10)
+    (0 T0 A0, 1 T1 A1, 2 T2 A2, 3 T3 A3, 4 T4 A4, 5 T5 A5, 6 T6 A6, 7 T7 A7, 8 T8 A8, 9 T9 A9, 10 T10 A10, 11 T11 A11)
+}
+
+#[cfg(feature = "chrono")]
+impl ToXml for DateTime<Utc> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        let element = match field {
+            Some(id) => {
+                let element = serializer.write_start(id.name, id.ns, None::<Context<0>>)?;
+                serializer.end_start()?;
+                Some(element)
+            }
+            None => None,
+        };
+
+        serializer.write_str(&self.to_rfc3339())?;
+        if let Some(element) = element {
+            serializer.write_close(element)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'xml> FromXml<'xml> for DateTime<Utc> {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        let Some(value) = deserializer.take_str()? else {
+            return Ok(());
+        };
+
+        match DateTime::parse_from_rfc3339(value.as_ref()) {
+            Ok(dt) if dt.timezone().utc_minus_local() == 0 => {
+                *into = Some(dt.with_timezone(&Utc));
+                Ok(())
+            }
+            _ => Err(Error::Other("invalid date/time".into())),
+        }
+    }
+
+    type Accumulator = Option<Self>;
+    const KIND: Kind = Kind::Scalar;
+}
+
+#[cfg(feature = "chrono")]
 impl ToXml for NaiveDateTime {
     fn serialize<W: fmt::Write + ?Sized>(
         &self,
@@ -793,7 +1352,12 @@ impl<'xml> FromXml<'xml> for NaiveDate {
             return Ok(());
         };
 
-        match Self::parse_from_str(value.as_ref(), "%Y-%m-%d") {
+        let date = match crate::xsd::split_offset(value.as_ref()) {
+            Some((date, _)) => Self::parse_from_str(date, "%Y-%m-%d"),
+            None => return Err(Error::Other("invalid date/time".into())),
+        };
+
+        match date {
             Ok(d) => {
                 *into = Some(d);
                 Ok(())
@@ -806,6 +1370,317 @@ impl<'xml> FromXml<'xml> for NaiveDate {
     const KIND: Kind = Kind::Scalar;
 }
 
+#[cfg(feature = "chrono")]
+impl ToXml for DateTime<FixedOffset> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        display_to_xml(&self.to_rfc3339(), field, serializer)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'xml> FromXml<'xml> for DateTime<FixedOffset> {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        let Some(value) = deserializer.take_str()? else {
+            return Ok(());
+        };
+
+        match Self::parse_from_rfc3339(value.as_ref()) {
+            Ok(dt) => {
+                *into = Some(dt);
+                Ok(())
+            }
+            _ => Err(Error::Other("invalid date/time".into())),
+        }
+    }
+
+    type Accumulator = Option<Self>;
+    const KIND: Kind = Kind::Scalar;
+}
+
+#[cfg(feature = "chrono")]
+impl ToXml for DateTime<Local> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        display_to_xml(&self.to_rfc3339(), field, serializer)
+    }
+}
+
+/// Values with any offset are accepted and converted to the local timezone
+#[cfg(feature = "chrono")]
+impl<'xml> FromXml<'xml> for DateTime<Local> {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        let Some(value) = deserializer.take_str()? else {
+            return Ok(());
+        };
+
+        match DateTime::parse_from_rfc3339(value.as_ref()) {
+            Ok(dt) => {
+                *into = Some(dt.with_timezone(&Local));
+                Ok(())
+            }
+            _ => Err(Error::Other("invalid date/time".into())),
+        }
+    }
+
+    type Accumulator = Option<Self>;
+    const KIND: Kind = Kind::Scalar;
+}
+
+#[cfg(feature = "chrono")]
+impl ToXml for NaiveTime {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        display_to_xml(&self.format("%H:%M:%S%.f"), field, serializer)
+    }
+}
+
+/// Parses `xs:time` values; a timezone suffix is accepted but discarded
+#[cfg(feature = "chrono")]
+impl<'xml> FromXml<'xml> for NaiveTime {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        let Some(value) = deserializer.take_str()? else {
+            return Ok(());
+        };
+
+        let time = match crate::xsd::split_offset(value.as_ref()) {
+            Some((time, _)) => Self::parse_from_str(time, "%H:%M:%S%.f"),
+            None => return Err(Error::Other("invalid date/time".into())),
+        };
+
+        match time {
+            Ok(t) => {
+                *into = Some(t);
+                Ok(())
+            }
+            _ => Err(Error::Other("invalid date/time".into())),
+        }
+    }
+
+    type Accumulator = Option<Self>;
+    const KIND: Kind = Kind::Scalar;
+}
+
+/// Implements scalar `FromXml` and `ToXml` for a date/time type
+///
+/// `$parse` evaluates to an `Option<Self>` given the text content; `$display` evaluates to a
+/// `Result` with a `Display` value to write.
+#[cfg(any(feature = "time", feature = "jiff"))]
+macro_rules! xml_for_datetime {
+    ($ty:ty, |$value:ident| $parse:expr, |$this:ident| $display:expr) => {
+        impl ToXml for $ty {
+            fn serialize<W: fmt::Write + ?Sized>(
+                &self,
+                field: Option<Id<'_>>,
+                serializer: &mut Serializer<'_, W>,
+            ) -> Result<(), Error> {
+                let $this = self;
+                display_to_xml(&$display?, field, serializer)
+            }
+        }
+
+        impl<'xml> FromXml<'xml> for $ty {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let Some(value) = deserializer.take_str()? else {
+                    return Ok(());
+                };
+
+                let parse = |$value: &str| -> Option<Self> { $parse };
+                match parse(value.as_ref()) {
+                    Some(value) => {
+                        *into = Some(value);
+                        Ok(())
+                    }
+                    None => Err(Error::Other("invalid date/time".into())),
+                }
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = Kind::Scalar;
+        }
+    };
+}
+
+#[cfg(feature = "time")]
+xml_for_datetime!(
+    OffsetDateTime,
+    |value| Self::parse(value, &Rfc3339).ok(),
+    |this| this
+        .format(&Rfc3339)
+        .map_err(|e| Error::Other(e.to_string()))
+);
+
+#[cfg(feature = "time")]
+xml_for_datetime!(
+    PrimitiveDateTime,
+    |value| {
+        let format = format_description!(
+            "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+        );
+        Self::parse(value, format).ok()
+    },
+    |this| {
+        let format = match this.nanosecond() {
+            0 => format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
+            _ => format_description!(
+                "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:1+]"
+            ),
+        };
+        this.format(format).map_err(|e| Error::Other(e.to_string()))
+    }
+);
+
+// Parses `xs:date` values; a timezone suffix is accepted but discarded
+#[cfg(feature = "time")]
+xml_for_datetime!(
+    time::Date,
+    |value| {
+        let (date, _) = crate::xsd::split_offset(value)?;
+        Self::parse(date, format_description!("[year]-[month]-[day]")).ok()
+    },
+    |this| {
+        this.format(format_description!("[year]-[month]-[day]"))
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+);
+
+// Parses `xs:time` values; a timezone suffix is accepted but discarded
+#[cfg(feature = "time")]
+xml_for_datetime!(
+    time::Time,
+    |value| {
+        let (time, _) = crate::xsd::split_offset(value)?;
+        let format = format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+        Self::parse(time, format).ok()
+    },
+    |this| {
+        let format = match this.nanosecond() {
+            0 => format_description!("[hour]:[minute]:[second]"),
+            _ => format_description!("[hour]:[minute]:[second].[subsecond digits:1+]"),
+        };
+        this.format(format).map_err(|e| Error::Other(e.to_string()))
+    }
+);
+
+#[cfg(feature = "jiff")]
+xml_for_datetime!(
+    Timestamp,
+    |value| value.parse().ok(),
+    |this| Ok::<_, Error>(this)
+);
+
+// Serialized as an `xs:dateTime` with the zone's offset, dropping any time zone name. Values
+// with a bracketed time zone annotation are accepted as well.
+#[cfg(feature = "jiff")]
+xml_for_datetime!(
+    Zoned,
+    |value| match value.parse() {
+        Ok(zoned) => Some(zoned),
+        Err(_) => {
+            let (_, offset) = crate::xsd::split_offset(value)?;
+            let offset = tz::Offset::from_seconds(i32::from(offset?) * 60).ok()?;
+            let timestamp = value.parse::<Timestamp>().ok()?;
+            Some(timestamp.to_zoned(tz::TimeZone::fixed(offset)))
+        }
+    },
+    |this| Ok::<_, Error>(this.timestamp().display_with_offset(this.offset()))
+);
+
+#[cfg(feature = "jiff")]
+xml_for_datetime!(civil::DateTime, |value| value.parse().ok(), |this| Ok::<
+    _,
+    Error,
+>(this));
+
+// Parses `xs:date` values; a timezone suffix is accepted but discarded
+#[cfg(feature = "jiff")]
+xml_for_datetime!(
+    civil::Date,
+    |value| crate::xsd::split_offset(value)?.0.parse().ok(),
+    |this| Ok::<_, Error>(this)
+);
+
+// Parses `xs:time` values; a timezone suffix is accepted but discarded
+#[cfg(feature = "jiff")]
+xml_for_datetime!(
+    civil::Time,
+    |value| crate::xsd::split_offset(value)?.0.parse().ok(),
+    |this| Ok::<_, Error>(this)
+);
+
 impl<'xml> FromXml<'xml> for () {
     #[inline]
     fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
@@ -828,6 +1703,52 @@ impl<'xml> FromXml<'xml> for () {
     const KIND: Kind = Kind::Scalar;
 }
 
+/// Implements scalar `FromXml` for a type with a `FromStr` impl
+///
+/// Surrounding whitespace is ignored, since it is never part of these values.
+macro_rules! from_xml_for_from_str {
+    ($typ:ty) => {
+        impl<'xml> FromXml<'xml> for $typ {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let Some(value) = deserializer.take_str()? else {
+                    return Ok(());
+                };
+
+                let value = value.as_ref().trim();
+                match <$typ>::from_str(value) {
+                    Ok(parsed) => {
+                        *into = Some(parsed);
+                        Ok(())
+                    }
+                    Err(_) => Err(Error::UnexpectedValue(format!(
+                        "unable to parse {} from `{value}` for {field}",
+                        type_name::<$typ>()
+                    ))),
+                }
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = Kind::Scalar;
+        }
+    };
+}
+
 impl ToXml for IpAddr {
     fn serialize<W: fmt::Write + ?Sized>(
         &self,
@@ -838,7 +1759,155 @@ impl ToXml for IpAddr {
     }
 }
 
-impl<'xml> FromXml<'xml> for IpAddr {
+from_xml_for_from_str!(IpAddr);
+
+/// Implements `FromXml` and `ToXml` for a network type of a single address family
+///
+/// Values of the other family are rejected with an error naming both families.
+macro_rules! xml_for_net_family {
+    ($typ:ty, $any:ty, $family:literal, $other:literal) => {
+        impl ToXml for $typ {
+            fn serialize<W: fmt::Write + ?Sized>(
+                &self,
+                field: Option<Id<'_>>,
+                serializer: &mut Serializer<'_, W>,
+            ) -> Result<(), Error> {
+                DisplayToXml(self).serialize(field, serializer)
+            }
+        }
+
+        impl<'xml> FromXml<'xml> for $typ {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                if into.is_some() {
+                    return Err(Error::DuplicateValue(field));
+                }
+
+                let Some(value) = deserializer.take_str()? else {
+                    return Ok(());
+                };
+
+                let value = value.as_ref().trim();
+                if let Ok(parsed) = <$typ>::from_str(value) {
+                    *into = Some(parsed);
+                    return Ok(());
+                }
+
+                Err(Error::UnexpectedValue(match <$any>::from_str(value) {
+                    Ok(_) => format!(
+                        "expected {} but found {} `{value}` for {field}",
+                        $family, $other
+                    ),
+                    Err(_) => format!("unable to parse {} from `{value}` for {field}", $family),
+                }))
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = Kind::Scalar;
+        }
+    };
+}
+
+xml_for_net_family!(Ipv4Addr, IpAddr, "IPv4 address", "IPv6 address");
+xml_for_net_family!(Ipv6Addr, IpAddr, "IPv6 address", "IPv4 address");
+xml_for_net_family!(
+    SocketAddrV4,
+    SocketAddr,
+    "IPv4 socket address",
+    "IPv6 socket address"
+);
+xml_for_net_family!(
+    SocketAddrV6,
+    SocketAddr,
+    "IPv6 socket address",
+    "IPv4 socket address"
+);
+
+impl ToXml for SocketAddr {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        DisplayToXml(self).serialize(field, serializer)
+    }
+}
+
+from_xml_for_from_str!(SocketAddr);
+
+#[cfg(feature = "ipnet")]
+xml_for_net_family!(Ipv4Net, IpNet, "IPv4 network", "IPv6 network");
+#[cfg(feature = "ipnet")]
+xml_for_net_family!(Ipv6Net, IpNet, "IPv6 network", "IPv4 network");
+
+#[cfg(feature = "ipnet")]
+impl ToXml for IpNet {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        DisplayToXml(self).serialize(field, serializer)
+    }
+}
+
+#[cfg(feature = "ipnet")]
+from_xml_for_from_str!(IpNet);
+
+#[cfg(feature = "uuid")]
+impl ToXml for Uuid {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        DisplayToXml(&self.hyphenated()).serialize(field, serializer)
+    }
+}
+
+#[cfg(feature = "uuid")]
+from_xml_for_from_str!(Uuid);
+
+// URLs may contain characters such as `&`, so they are serialized as escaped strings
+#[cfg(feature = "url")]
+impl ToXml for Url {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        self.as_str().serialize(field, serializer)
+    }
+}
+
+#[cfg(feature = "url")]
+from_xml_for_from_str!(Url);
+
+#[cfg(feature = "rust_decimal")]
+impl ToXml for Decimal {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        DisplayToXml(self).serialize(field, serializer)
+    }
+}
+
+/// Accepts `xs:decimal` lexical forms, preserving the scale of the input
+#[cfg(feature = "rust_decimal")]
+impl<'xml> FromXml<'xml> for Decimal {
     #[inline]
     fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
         match field {
@@ -856,10 +1925,18 @@ impl<'xml> FromXml<'xml> for IpAddr {
             return Err(Error::DuplicateValue(field));
         }
 
-        let mut value = None;
-        FromXmlStr::<Self>::deserialize(&mut value, field, deserializer)?;
-        if let Some(value) = value {
-            *into = Some(value.0);
+        let Some(value) = deserializer.take_str()? else {
+            return Ok(());
+        };
+
+        match crate::xsd::decimal_lexical(value.as_ref()).map(Self::from_str) {
+            Some(Ok(decimal)) => *into = Some(decimal),
+            _ => {
+                return Err(Error::UnexpectedValue(format!(
+                    "unable to parse number {} from `{value}` for {field}",
+                    type_name::<Self>()
+                )))
+            }
         }
 
         Ok(())
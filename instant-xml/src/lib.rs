@@ -120,9 +120,9 @@
 //!   assert_eq!(to_string(&inline).unwrap(), "<Foo /><Bar />");
 //!   ```
 //!
-//! - **`scalar`** *(enums only)* - serializes variants as text content.
+//! - **`scalar`** - (de)serializes the value as text content rather than as an element.
 //!
-//!   The enum must only have unit variants.
+//!   On enums, all variants must be unit variants.
 //!
 //!   ```
 //!   # use instant_xml::{ToXml, to_string};
@@ -145,6 +145,50 @@
 //!
 //!   Variants can use `#[xml(rename = "...")]` or string/integer discriminants.
 //!
+//!   On structs, a newtype delegates to its inner scalar type, while any other struct is
+//!   (de)serialized through its `Display` and `FromStr` implementations, with the output escaped
+//!   like a string. Either way, the type can be used as an attribute or as element content.
+//!
+//!   ```
+//!   # use instant_xml::{ToXml, to_string};
+//!   #[derive(ToXml)]
+//!   #[xml(scalar)]
+//!   struct Sku(String);
+//!
+//!   #[derive(ToXml)]
+//!   struct Item {
+//!       #[xml(attribute)]
+//!       sku: Sku,
+//!   }
+//!
+//!   let item = Item { sku: Sku("A-1".to_string()) };
+//!   assert_eq!(to_string(&item).unwrap(), r#"<Item sku="A-1" />"#);
+//!   ```
+//!
+//! - **`choice`** *(enums only)* - serializes variants as empty child elements.
+//!
+//!   The enum must only have unit variants. Variant elements use the enum's namespace if one
+//!   is set, and otherwise inherit the namespace of the element they are nested in.
+//!
+//!   ```
+//!   # use instant_xml::{ToXml, to_string};
+//!
+//!   #[derive(ToXml)]
+//!   struct Container {
+//!       status: Status,
+//!   }
+//!
+//!   #[derive(ToXml)]
+//!   #[xml(choice, rename_all = "lowercase")]
+//!   enum Status {
+//!       Active,
+//!       Inactive,
+//!   }
+//!
+//!   let c = Container { status: Status::Active };
+//!   assert_eq!(to_string(&c).unwrap(), "<Container><status><active /></status></Container>");
+//!   ```
+//!
 //! - **`forward`** *(enums only)* - forwards to inner type's element name.
 //!
 //!   Each variant must contain exactly one unnamed field.
@@ -172,6 +216,109 @@
 //! -**`force_prefix`** *(structs only)* - Always serialize a namespace prefix if one is set for this element's namespace.
 //! Does not affect deserialization.
 //!
+//! - **`ordered`** *(structs only)* - requires child elements to appear in field declaration
+//!   order, like an `xs:sequence`
+//!
+//!   Optional and repeated fields may be skipped, and unknown elements are still ignored.
+//!   Elements out of order are reported as [`Error::OutOfOrder`].
+//!
+//!   ```
+//!   # use instant_xml::{Error, FromXml, from_str};
+//!   #[derive(Debug, FromXml)]
+//!   #[xml(ordered)]
+//!   struct Name {
+//!       first: String,
+//!       last: String,
+//!   }
+//!
+//!   assert!(from_str::<Name>("<Name><first>A</first><last>B</last></Name>").is_ok());
+//!   assert_eq!(
+//!       from_str::<Name>("<Name><last>B</last><first>A</first></Name>").unwrap_err(),
+//!       Error::OutOfOrder {
+//!           element: "first".to_owned(),
+//!           field: "Name::first",
+//!           expected: "last",
+//!       },
+//!   );
+//!   ```
+//!
+//! - **`any_name`** *(structs only)* - matches elements with any name, instead of only the
+//!   container's own tag
+//!
+//!   Usually combined with a `tag_name` field to capture the name. As a field in another struct,
+//!   the type is only offered the child elements that no other field matches (see
+//!   [`FromXml::ANY_NAME`]). When serializing, the `tag_name` value must be a valid element
+//!   name without a prefix.
+//!
+//! - **`from = "Type"`**, **`try_from = "Type"`** and **`into = "Type"`** - (de)serialize
+//!   through an intermediate type
+//!
+//!   `FromXml` deserializes `Type` and converts it using `From` or `TryFrom`; errors from
+//!   `TryFrom` must implement `Display` and are reported as [`Error::UnexpectedValue`]. `ToXml`
+//!   converts a clone of the value using `Into` before serializing it, so the value is always
+//!   considered present. All other container attributes are ignored in favor of those of `Type`.
+//!
+//!   ```
+//!   # use instant_xml::{FromXml, ToXml, from_str, to_string};
+//!   #[derive(FromXml, ToXml)]
+//!   #[xml(rename = "point")]
+//!   struct PointWire {
+//!       #[xml(attribute)]
+//!       x: i32,
+//!       #[xml(attribute)]
+//!       y: i32,
+//!   }
+//!
+//!   #[derive(Clone, Debug, PartialEq, FromXml, ToXml)]
+//!   #[xml(from = "PointWire", into = "PointWire")]
+//!   struct Point(i32, i32);
+//!
+//!   impl From<PointWire> for Point {
+//!       fn from(wire: PointWire) -> Self {
+//!           Self(wire.x, wire.y)
+//!       }
+//!   }
+//!
+//!   impl From<Point> for PointWire {
+//!       fn from(point: Point) -> Self {
+//!           Self { x: point.0, y: point.1 }
+//!       }
+//!   }
+//!
+//!   let xml = r#"<point x="1" y="2" />"#;
+//!   assert_eq!(to_string(&Point(1, 2)).unwrap(), xml);
+//!   assert_eq!(from_str::<Point>(xml).unwrap(), Point(1, 2));
+//!   ```
+//!
+//! - **`validate = "path"`** *(structs only)* - checks the value after deserialization
+//!
+//!   The function has the signature `fn(&Self) -> Result<(), E>` where `E: Display`; errors
+//!   are reported as [`Error::Validation`] with the type name as the field.
+//!
+//!   ```
+//!   # use instant_xml::{FromXml, from_str};
+//!   #[derive(Debug, FromXml)]
+//!   #[xml(validate = "Range::check")]
+//!   struct Range {
+//!       #[xml(attribute)]
+//!       min: u32,
+//!       #[xml(attribute)]
+//!       max: u32,
+//!   }
+//!
+//!   impl Range {
+//!       fn check(&self) -> Result<(), &'static str> {
+//!           match self.min <= self.max {
+//!               true => Ok(()),
+//!               false => Err("min exceeds max"),
+//!           }
+//!       }
+//!   }
+//!
+//!   assert!(from_str::<Range>(r#"<Range min="1" max="2" />"#).is_ok());
+//!   assert!(from_str::<Range>(r#"<Range min="3" max="2" />"#).is_err());
+//!   ```
+//!
 //! ## Field attributes
 //!
 //! Applied to struct fields using `#[xml(...)]`:
@@ -206,6 +353,144 @@
 //!   assert_eq!(to_string(&p).unwrap(), r#"<Paragraph lang="en">Hello</Paragraph>"#);
 //!   ```
 //!
+//! - **`flatten`** - merges the attributes and child elements of another derived struct into
+//!   this element
+//!
+//!   The flattened struct must have named fields and no `direct` field; see [`FromXmlFlatten`]
+//!   and [`ToXmlFlatten`]. When deserializing, attributes and elements that don't match any of
+//!   the parent's own fields are offered to the flattened fields in order.
+//!
+//!   ```
+//!   # use instant_xml::{FromXml, ToXml, from_str, to_string};
+//!   #[derive(Debug, PartialEq, FromXml, ToXml)]
+//!   struct Audit {
+//!       #[xml(attribute)]
+//!       revision: u32,
+//!       created: String,
+//!   }
+//!
+//!   #[derive(Debug, PartialEq, FromXml, ToXml)]
+//!   struct Document {
+//!       title: String,
+//!       #[xml(flatten)]
+//!       audit: Audit,
+//!   }
+//!
+//!   let doc = Document {
+//!       title: "Notes".to_string(),
+//!       audit: Audit { revision: 3, created: "2024-01-01".to_string() },
+//!   };
+//!
+//!   let xml = r#"<Document revision="3"><title>Notes</title><created>2024-01-01</created></Document>"#;
+//!   assert_eq!(to_string(&doc).unwrap(), xml);
+//!   assert_eq!(from_str::<Document>(xml).unwrap(), doc);
+//!   ```
+//!
+//! - **`list`** - (de)serializes a `Vec<T>` of scalars as a whitespace-separated list (`xs:list`)
+//!
+//!   Can be combined with `attribute` and `direct`. See [`List`] for a wrapper type with the
+//!   same behavior.
+//!
+//!   ```
+//!   # use instant_xml::{ToXml, to_string};
+//!   #[derive(ToXml)]
+//!   struct Polygon {
+//!       #[xml(attribute, list)]
+//!       class: Vec<String>,
+//!       #[xml(list)]
+//!       coords: Vec<i32>,
+//!   }
+//!
+//!   let p = Polygon { class: vec!["a".into(), "b".into()], coords: vec![1, 2, 3] };
+//!   assert_eq!(
+//!       to_string(&p).unwrap(),
+//!       r#"<Polygon class="a b"><coords>1 2 3</coords></Polygon>"#
+//!   );
+//!   ```
+//!
+//! - **`base64`** / **`hex`** - (de)serializes a `Vec<u8>` as base64 or hexadecimal text
+//!
+//!   Whitespace and line breaks in base64 content are ignored when deserializing. Encoded
+//!   content is written directly to the output. Can be combined with `attribute` and `direct`.
+//!   See [`xsd::Base64Binary`] and [`xsd::HexBinary`] for wrapper types with the same encoding.
+//!
+//!   ```
+//!   # use instant_xml::{ToXml, to_string};
+//!   #[derive(ToXml)]
+//!   struct Blob {
+//!       #[xml(attribute, hex)]
+//!       digest: Vec<u8>,
+//!       #[xml(direct, base64)]
+//!       data: Vec<u8>,
+//!   }
+//!
+//!   let blob = Blob { digest: vec![0xca, 0xfe], data: b"hello".to_vec() };
+//!   assert_eq!(to_string(&blob).unwrap(), r#"<Blob digest="CAFE">aGVsbG8=</Blob>"#);
+//!   ```
+//!
+//! - **`format = "..."`** - (de)serializes a value using a custom format string
+//!
+//!   Supported by types implementing [`FromXmlFormat`] and [`ToXmlFormat`], which includes the
+//!   `chrono` and `jiff` date and time types (with `strftime`-style formats such as
+//!   `"%d/%m/%Y %H:%M"`) and the `time` types (with format descriptions such as
+//!   `"[day]/[month]/[year]"`).
+//!   Can be combined with `attribute` and `direct`.
+//!
+//! - **`display_fromstr`** - (de)serializes a value through its `Display` and `FromStr`
+//!   implementations
+//!
+//!   Works for `T` and `Option<T>` and can be combined with `attribute` and `direct`. See
+//!   [`DisplayFromStr`] for use outside of the derive macros.
+//!
+//! - **`wrapper = "name"`** - nests a collection's elements inside a container element
+//!
+//!   The wrapper element uses the field's namespace. When deserializing, a missing or empty
+//!   wrapper element yields an empty collection. For `Option` fields, `None` skips the wrapper
+//!   and a missing wrapper yields `None`, while an empty one yields an empty collection.
+//!
+//!   ```
+//!   # use instant_xml::{ToXml, to_string};
+//!   #[derive(ToXml)]
+//!   struct Order {
+//!       #[xml(wrapper = "items", rename = "item")]
+//!       items: Vec<u32>,
+//!   }
+//!
+//!   let order = Order { items: vec![1, 2] };
+//!   assert_eq!(
+//!       to_string(&order).unwrap(),
+//!       "<Order><items><item>1</item><item>2</item></items></Order>"
+//!   );
+//!   ```
+//!
+//! - **`key_attribute = "name"`** - (de)serializes a `HashMap` or `BTreeMap` as repeated
+//!   entry elements with the key in the given attribute (`<entry key="k">v</entry>`)
+//!
+//! - **`key_element = "name", value_element = "name"`** - (de)serializes a `HashMap` or
+//!   `BTreeMap` as repeated entry elements with key and value child elements
+//!   (`<entry><key>k</key><value>v</value></entry>`)
+//!
+//!   Without these attributes, maps use the field's element and its children's names as keys
+//!   (`<field><k>v</k></field>`), so each key must serialize to a valid XML name without
+//!   colons. Map layouts can be combined with `wrapper`. Duplicate keys
+//!   result in [`Error::DuplicateValue`]. See the [`map`] module for details.
+//!
+//!   ```
+//!   # use std::collections::BTreeMap;
+//!   # use instant_xml::{ToXml, to_string};
+//!   #[derive(ToXml)]
+//!   struct Config {
+//!       #[xml(wrapper = "options", rename = "option", key_attribute = "name")]
+//!       options: BTreeMap<String, u32>,
+//!   }
+//!
+//!   let config = Config { options: BTreeMap::from([("depth".to_owned(), 3)]) };
+//!   assert_eq!(
+//!       to_string(&config).unwrap(),
+//!       r#"<Config><options><option name="depth">3</option></options></Config>"#
+//!   );
+//!   ```
+//!
 //! - **`rename = "name"`** - renames the field's element or attribute name
 //!
 //! - **`ns("uri")`** - sets namespace for this specific field
@@ -312,6 +597,130 @@
 //!   assert_eq!(config.enabled, true);
 //!   ```
 //!
+//!   Both functions can also be used on `attribute` and `direct` fields, where they (de)serialize
+//!   the attribute value or text content. Attribute values written by a serialization function
+//!   are escaped by [`Serializer::write_str()`]; text content is written as-is.
+//!
+//! - **`with = "module"`** - shorthand for `serialize_with = "module::serialize"` and
+//!   `deserialize_with = "module::deserialize"`
+//!
+//!   ```
+//!   # use instant_xml::{FromXml, ToXml, from_str, to_string};
+//!   #[derive(Debug, PartialEq, FromXml, ToXml)]
+//!   struct Config {
+//!       #[xml(attribute, with = "yes_no")]
+//!       enabled: bool,
+//!   }
+//!
+//!   mod yes_no {
+//!       # use instant_xml::{Deserializer, Error, Serializer};
+//!       # use std::fmt;
+//!       pub fn serialize<W: fmt::Write + ?Sized>(
+//!           value: &bool,
+//!           serializer: &mut Serializer<'_, W>,
+//!       ) -> Result<(), Error> {
+//!           serializer.write_str(if *value { "yes" } else { "no" })
+//!       }
+//!
+//!       pub fn deserialize(
+//!           accumulator: &mut Option<bool>,
+//!           field: &'static str,
+//!           deserializer: &mut Deserializer<'_, '_>,
+//!       ) -> Result<(), Error> {
+//!           *accumulator = match deserializer.take_str()?.as_deref() {
+//!               Some("yes") => Some(true),
+//!               Some("no") => Some(false),
+//!               _ => return Err(Error::UnexpectedValue(format!("invalid value for {field}"))),
+//!           };
+//!           Ok(())
+//!       }
+//!   }
+//!
+//!   let xml = r#"<Config enabled="yes" />"#;
+//!   assert_eq!(to_string(&Config { enabled: true }).unwrap(), xml);
+//!   assert_eq!(from_str::<Config>(xml).unwrap(), Config { enabled: true });
+//!   ```
+//!
+//! - **`min_len = N`** / **`max_len = N`** - bounds the length of a deserialized value
+//!
+//!   Text is measured in characters and collections in items (see [`validate::Length`]); an
+//!   absent `Option` always passes. Failures are reported as [`Error::Validation`].
+//!
+//!   ```
+//!   # use instant_xml::{FromXml, from_str};
+//!   #[derive(Debug, FromXml)]
+//!   struct Team {
+//!       #[xml(attribute, min_len = 1, max_len = 8)]
+//!       name: String,
+//!       #[xml(rename = "member", min_len = 1)]
+//!       members: Vec<String>,
+//!   }
+//!
+//!   assert!(from_str::<Team>(r#"<Team name="rust"><member>ferris</member></Team>"#).is_ok());
+//!   assert!(from_str::<Team>(r#"<Team name="rust"></Team>"#).is_err());
+//!   ```
+//!
+//! - **`min_occurs = N`** / **`max_occurs = N`** - bounds the number of elements in a
+//!   repeated element field such as a `Vec<T>`
+//!
+//!   Only collection types (optionally wrapped in `Option`) are accepted; see
+//!   [`validate::Occurs`].
+//!
+//!   `FromXml` reports [`Error::TooManyOccurrences`] as soon as the maximum is exceeded and
+//!   [`Error::TooFewOccurrences`] once the parent element is complete. `ToXml` checks both
+//!   bounds before writing anything.
+//!
+//!   ```
+//!   # use instant_xml::{Error, FromXml, ToXml, from_str, to_string};
+//!   #[derive(Debug, FromXml, ToXml)]
+//!   struct Playlist {
+//!       #[xml(rename = "track", min_occurs = 1, max_occurs = 2)]
+//!       tracks: Vec<String>,
+//!   }
+//!
+//!   let xml = "<Playlist><track>a</track><track>b</track><track>c</track></Playlist>";
+//!   assert_eq!(
+//!       from_str::<Playlist>(xml).unwrap_err(),
+//!       Error::TooManyOccurrences { field: "Playlist::tracks", count: 3, max: 2 },
+//!   );
+//!   assert!(to_string(&Playlist { tracks: vec![] }).is_err());
+//!   ```
+//!
+//! - **`pattern = "regex"`** - requires the deserialized text to match a regular expression
+//!
+//!   Requires the `regex` feature. As in XML Schema, the pattern must match the entire value.
+//!   Failures are reported as [`Error::Validation`].
+//!
+//! - **`tag_name`** - holds the element's own name, for elements with dynamic tags
+//!
+//!   The field type must implement [`TagName`], as `String`, `Cow<str>` and [`Id`] do.
+//!   `FromXml` stores the name of the deserialized element, and `ToXml` writes the element using
+//!   the field's name instead of the container's tag, returning [`Error::UnexpectedValue`] if it
+//!   is not a valid element name. An `Id` also carries the namespace. Only `borrow` may be
+//!   combined with it.
+//!
+//!   ```
+//!   # use instant_xml::{FromXml, ToXml, from_str, to_string};
+//!   #[derive(Debug, PartialEq, FromXml, ToXml)]
+//!   #[xml(any_name)]
+//!   struct Price {
+//!       #[xml(tag_name)]
+//!       currency: String,
+//!       #[xml(direct)]
+//!       amount: u32,
+//!   }
+//!
+//!   #[derive(Debug, PartialEq, FromXml, ToXml)]
+//!   struct Product {
+//!       prices: Vec<Price>,
+//!   }
+//!
+//!   let xml = "<Product><price-USD>10</price-USD><price-EUR>9</price-EUR></Product>";
+//!   let product = from_str::<Product>(xml).unwrap();
+//!   assert_eq!(product.prices[1].currency, "price-EUR");
+//!   assert_eq!(to_string(&product).unwrap(), xml);
+//!   ```
+//!
 //! - **`borrow`** - Borrows from input during deserialization. Automatically applies to
 //!   top-level `&str` and `&[u8]` fields. Useful for `Cow<str>` and similar types.
 //!
@@ -328,7 +737,28 @@
 //!   let parsed = from_str::<Borrowed>(xml).unwrap();
 //!   assert_eq!(parsed.text, "Hello");
 //!   ```
+//!
+//! ## Tuple structs and tuples
+//!
+//! The fields of tuple structs are (de)serialized as the element's content, in order. They
+//! support the `attribute`, `direct`, `rename`, `ns`, `with`, `serialize_with` and
+//! `deserialize_with` field attributes; `attribute` and `ns` require a `rename`. Renamed
+//! content fields are wrapped in an element of that name, even for scalar types.
+//!
+//! ```
+//! # use instant_xml::{FromXml, ToXml, from_str, to_string};
+//! #[derive(Debug, PartialEq, FromXml, ToXml)]
+//! struct Price(#[xml(attribute, rename = "currency")] String, #[xml(direct)] u32);
+//!
+//! let xml = r#"<Price currency="EUR">42</Price>"#;
+//! assert_eq!(to_string(&Price("EUR".to_owned(), 42)).unwrap(), xml);
+//! assert_eq!(from_str::<Price>(xml).unwrap(), Price("EUR".to_owned(), 42));
+//! ```
+//!
+//! Rust tuples of up to 12 items are (de)serialized as a sequence of elements, one per item
+//! and in order.
 
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::{borrow::Cow, fmt};
 
 use thiserror::Error;
@@ -337,12 +767,29 @@ pub use macros::{FromXml, ToXml};
 
 pub mod de;
 mod impls;
-pub use de::Deserializer;
-pub use impls::{display_to_xml, from_xml_str, OptionAccumulator};
+pub use de::{Deserializer, Fragments};
+pub use impls::{
+    display_to_xml, from_xml_str, DisplayFromStr, OptionAccumulator, TupleAccumulator,
+};
 pub mod ser;
 pub use ser::Serializer;
 mod any_element;
 pub use any_element::{AnyAttribute, AnyElement};
+mod binary;
+pub use binary::{from_xml_base64, from_xml_hex, Base64Ref, HexRef};
+mod flatten;
+pub use flatten::{FromXmlFlatten, ToXmlFlatten};
+mod format;
+pub use format::{Formatted, FromXmlFormat, ToXmlFormat};
+mod list;
+pub use list::{from_xml_list, List, ListRef};
+mod stream;
+pub use stream::{StreamEvent, StreamHeader, StreamParser};
+mod tag_name;
+pub use tag_name::TagName;
+pub mod map;
+pub mod validate;
+pub mod xsd;
 
 /// Serialize a type to XML
 pub trait ToXml {
@@ -387,6 +834,10 @@ pub trait FromXml<'xml>: Sized {
     type Accumulator: Accumulate<Self>;
     /// The kind of XML node this type represents
     const KIND: Kind;
+    /// Whether [`FromXml::matches`] accepts elements regardless of their name
+    ///
+    /// Fields of such types are only offered the elements that no other field matches.
+    const ANY_NAME: bool = false;
 }
 
 /// Accumulate values during deserialization
@@ -409,6 +860,34 @@ impl<T> Accumulate<Self> for Vec<T> {
     }
 }
 
+impl<T, const N: usize> Accumulate<[T; N]> for Vec<T> {
+    fn try_done(self, field: &'static str) -> Result<[T; N], Error> {
+        <[T; N]>::try_from(self).map_err(|items| Error::TooFewOccurrences {
+            field,
+            count: items.len(),
+            min: N,
+        })
+    }
+}
+
+impl<T> Accumulate<Self> for VecDeque<T> {
+    fn try_done(self, _: &'static str) -> Result<Self, Error> {
+        Ok(self)
+    }
+}
+
+impl<T> Accumulate<Self> for BTreeSet<T> {
+    fn try_done(self, _: &'static str) -> Result<Self, Error> {
+        Ok(self)
+    }
+}
+
+impl<T, S: Default> Accumulate<Self> for HashSet<T, S> {
+    fn try_done(self, _: &'static str) -> Result<Self, Error> {
+        Ok(self)
+    }
+}
+
 impl<'a, T> Accumulate<Cow<'a, [T]>> for Vec<T>
 where
     [T]: ToOwned<Owned = Self>,
@@ -427,10 +906,30 @@ impl<T> Accumulate<Self> for Option<T> {
 /// Deserialize a type from an XML string
 ///
 /// This is a convenience function that creates a `Deserializer` and calls `deserialize()`.
+/// Content other than whitespace, comments and processing instructions after the root element
+/// is rejected with [`Error::TrailingContent`]; use
+/// [`Deserializer::allow_trailing_content()`] to ignore it instead.
 pub fn from_str<'xml, T: FromXml<'xml>>(input: &'xml str) -> Result<T, Error> {
     Deserializer::new(input)?.deserialize()
 }
 
+/// Deserialize consecutive top-level elements from an XML fragment
+///
+/// Useful for input without a single root element, such as concatenated documents like
+/// `<event /><event />`. Whitespace and comments between the elements are skipped, but XML
+/// declarations are not supported. Iteration ends after the first error.
+pub fn from_str_fragment<'xml, T: FromXml<'xml>>(input: &'xml str) -> Fragments<'xml, T> {
+    Fragments::new(input)
+}
+
+/// Deserialize a value from an XML string, with a root element of the given name
+///
+/// The name and namespace of `root` replace those the type would normally match, only for the
+/// root element.
+pub fn from_str_as<'xml, T: FromXml<'xml>>(input: &'xml str, root: Id<'_>) -> Result<T, Error> {
+    Deserializer::new(input)?.deserialize_as(root)
+}
+
 /// Serialize a value to an XML string
 pub fn to_string(value: &(impl ToXml + ?Sized)) -> Result<String, Error> {
     let mut output = String::new();
@@ -446,6 +945,25 @@ pub fn to_writer(
     value.serialize(None, &mut Serializer::new(output))
 }
 
+/// Serialize a value to an XML string, using the given name for the root element
+///
+/// The name and namespace of `root` replace the ones from the type, only for the root element;
+/// child elements keep their own namespace.
+pub fn to_string_as(value: &(impl ToXml + ?Sized), root: Id<'_>) -> Result<String, Error> {
+    let mut output = String::new();
+    to_writer_as(value, root, &mut output)?;
+    Ok(output)
+}
+
+/// Serialize a value to an XML writer, using the given name for the root element
+pub fn to_writer_as(
+    value: &(impl ToXml + ?Sized),
+    root: Id<'_>,
+    output: &mut (impl fmt::Write + ?Sized),
+) -> Result<(), Error> {
+    value.serialize(Some(root), &mut Serializer::new(output).with_root_name())
+}
+
 /// Marker trait for types that can be deserialized with any lifetime
 pub trait FromXmlOwned: for<'xml> FromXml<'xml> {}
 
@@ -469,6 +987,9 @@ pub enum Error {
     /// Unexpected end of XML stream
     #[error("unexpected end of stream")]
     UnexpectedEndOfStream,
+    /// Content other than whitespace, comments or processing instructions after the root element
+    #[error("trailing content after root element at {0}")]
+    TrailingContent(xmlparser::TextPos),
     /// Unexpected value encountered
     #[error("unexpected value: '{0}'")]
     UnexpectedValue(String),
@@ -499,6 +1020,44 @@ pub enum Error {
     /// Field value appears more than once
     #[error("duplicate value for {0}")]
     DuplicateValue(&'static str),
+    /// Deserialized value failed validation
+    #[error("validation failed for {field}: {message}")]
+    Validation {
+        /// The field (or type) that failed validation
+        field: &'static str,
+        /// Description of the failure
+        message: String,
+    },
+    /// Child element appears out of declaration order in an `ordered` struct
+    #[error("unexpected element `{element}` for {field}, expected {expected}")]
+    OutOfOrder {
+        /// The name of the out-of-order element
+        element: String,
+        /// The field the element belongs to
+        field: &'static str,
+        /// The names of the elements that may appear at this point
+        expected: &'static str,
+    },
+    /// Repeated field occurs fewer times than its `min_occurs`
+    #[error("{field} occurs {count} times, expected at least {min}")]
+    TooFewOccurrences {
+        /// The repeated field
+        field: &'static str,
+        /// The number of occurrences
+        count: usize,
+        /// The minimum number of occurrences
+        min: usize,
+    },
+    /// Repeated field occurs more times than its `max_occurs`
+    #[error("{field} occurs {count} times, expected at most {max}")]
+    TooManyOccurrences {
+        /// The repeated field
+        field: &'static str,
+        /// The number of occurrences
+        count: usize,
+        /// The maximum number of occurrences
+        max: usize,
+    },
 }
 
 /// The kind of XML node a type represents
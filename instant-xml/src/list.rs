@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::de::Node;
+use crate::ser::Context;
+use crate::{Accumulate, Deserializer, Error, FromXml, Id, Kind, Serializer, ToXml};
+
+/// A whitespace-separated list of scalar values (`xs:list`)
+///
+/// Deserializes text content or attribute values like `"1 2 3"` by splitting on XML whitespace
+/// and parsing each token with the item type's [`FromXml`] implementation. Serializes the items
+/// separated by a single space.
+///
+/// ```
+/// use instant_xml::{from_str, to_string, FromXml, List, ToXml};
+///
+/// #[derive(Debug, FromXml, PartialEq, ToXml)]
+/// struct Point {
+///     #[xml(attribute)]
+///     class: List<String>,
+///     coords: List<i32>,
+/// }
+///
+/// let xml = r#"<Point class="a b"><coords>1 2 3</coords></Point>"#;
+/// let point = from_str::<Point>(xml).unwrap();
+/// assert_eq!(point.class.0, ["a", "b"]);
+/// assert_eq!(point.coords.0, [1, 2, 3]);
+/// assert_eq!(to_string(&point).unwrap(), xml);
+/// ```
+///
+/// Items containing whitespace cannot be round-tripped. For `Vec<T>` fields, the
+/// `#[xml(list)]` field attribute provides the same behavior without the wrapper type.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for List<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self(items)
+    }
+}
+
+impl<T> From<List<T>> for Vec<T> {
+    fn from(list: List<T>) -> Self {
+        list.0
+    }
+}
+
+impl<'xml, T: FromXml<'xml>> FromXml<'xml> for List<T> {
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        *into = Some(Self(match deserializer.take_str()? {
+            Some(value) => parse_items(value, field, deserializer)?,
+            None => Vec::new(),
+        }));
+
+        Ok(())
+    }
+
+    type Accumulator = Option<Self>;
+    const KIND: Kind = Kind::Scalar;
+}
+
+impl<T: ToXml> ToXml for List<T> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        ListRef(&self.0).serialize(field, serializer)
+    }
+
+    fn present(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+/// A borrowed whitespace-separated list of scalar values (`xs:list`)
+///
+/// Serializes like [`List`], without requiring ownership of the items.
+#[derive(Clone, Copy, Debug)]
+pub struct ListRef<'a, T>(pub &'a [T]);
+
+impl<T: ToXml> ToXml for ListRef<'_, T> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        let element = match field {
+            Some(id) => {
+                let element = serializer.write_start(id.name, id.ns, None::<Context<0>>)?;
+                serializer.end_start()?;
+                Some(element)
+            }
+            None => None,
+        };
+
+        for (i, item) in self.0.iter().enumerate() {
+            if i > 0 {
+                serializer.write_str(" ")?;
+            }
+            item.serialize(None, serializer)?;
+        }
+
+        if let Some(element) = element {
+            serializer.write_close(element)?;
+        }
+
+        Ok(())
+    }
+
+    fn present(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+/// Deserialize a whitespace-separated list into a `Vec<T>`
+///
+/// Helper function used for `Vec<T>` fields with the `#[xml(list)]` attribute. An empty list
+/// cannot be told apart from a missing one, so the caller is responsible for rejecting
+/// duplicate elements.
+pub fn from_xml_list<'xml, T: FromXml<'xml>>(
+    into: &mut Vec<T>,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, 'xml>,
+) -> Result<(), Error> {
+    if let Some(value) = deserializer.take_str()? {
+        *into = parse_items(value, field, deserializer)?;
+    }
+
+    Ok(())
+}
+
+fn parse_items<'xml, T: FromXml<'xml>>(
+    value: Cow<'xml, str>,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, 'xml>,
+) -> Result<Vec<T>, Error> {
+    if T::KIND != Kind::Scalar {
+        return Err(Error::UnexpectedState("list items must be scalar values"));
+    }
+
+    let tokens = match value {
+        Cow::Borrowed(value) => value
+            .split_ascii_whitespace()
+            .map(Cow::Borrowed)
+            .collect::<Vec<_>>(),
+        Cow::Owned(value) => value
+            .split_ascii_whitespace()
+            .map(|token| Cow::Owned(token.to_owned()))
+            .collect(),
+    };
+
+    let mut items = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let mut value = T::Accumulator::default();
+        let mut nested = deserializer.for_node(Node::Text(token));
+        T::deserialize(&mut value, field, &mut nested)?;
+        items.push(value.try_done(field)?);
+    }
+
+    Ok(items)
+}
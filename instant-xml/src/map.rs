@@ -0,0 +1,388 @@
+//! Support code for map types
+//!
+//! By default, maps are (de)serialized with the keys as element names of the children of the
+//! field's element: `<field><key>value</key></field>`. Serialization fails unless each key is a
+//! valid XML name without colons (an `xs:NCName`). The functions in this module implement
+//! the alternative layouts selected by the `key_attribute` and `key_element`/`value_element`
+//! field attributes.
+
+use std::collections::btree_map::{self, BTreeMap};
+use std::collections::hash_map::{self, HashMap};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::de::Node;
+use crate::ser::Context;
+use crate::xsd::is_ncname;
+use crate::{Accumulate, Deserializer, Error, FromXml, Id, Kind, Serializer, ToXml};
+
+/// A map type that can be deserialized from XML
+pub trait Map: Default {
+    /// The key type
+    type Key;
+    /// The value type
+    type Value;
+
+    /// Insert a new entry, erroring if the key is already present
+    fn insert_unique(
+        &mut self,
+        key: Self::Key,
+        value: Self::Value,
+        field: &'static str,
+    ) -> Result<(), Error>;
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher + Default> Map for HashMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+
+    fn insert_unique(&mut self, key: K, value: V, field: &'static str) -> Result<(), Error> {
+        match self.entry(key) {
+            hash_map::Entry::Occupied(_) => Err(Error::DuplicateValue(field)),
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> Map for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn insert_unique(&mut self, key: K, value: V, field: &'static str) -> Result<(), Error> {
+        match self.entry(key) {
+            btree_map::Entry::Occupied(_) => Err(Error::DuplicateValue(field)),
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Deserialize a map entry keyed by an attribute: `<entry key="k">v</entry>`
+///
+/// The value is taken from the text content for scalar values, or from the matching child
+/// element otherwise.
+pub fn deserialize_keyed_entry<'xml, M>(
+    into: &mut M,
+    key: &str,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, 'xml>,
+) -> Result<(), Error>
+where
+    M: Map,
+    M::Key: FromXml<'xml>,
+    M::Value: FromXml<'xml>,
+{
+    let mut key_value = <M::Key as FromXml<'xml>>::Accumulator::default();
+    let mut value = <M::Value as FromXml<'xml>>::Accumulator::default();
+    let mut seen_value = false;
+    loop {
+        match deserializer.next() {
+            Some(Ok(Node::Attribute(attr))) => {
+                if deserializer.attribute_id(&attr)? == (Id { ns: "", name: key }) {
+                    let mut nested = deserializer.for_node(Node::AttributeValue(attr.value));
+                    M::Key::deserialize(&mut key_value, field, &mut nested)?;
+                }
+            }
+            Some(Ok(Node::Text(text))) if M::Value::KIND == Kind::Scalar => {
+                seen_value = true;
+                let mut nested = deserializer.for_node(Node::Text(text));
+                M::Value::deserialize(&mut value, field, &mut nested)?;
+            }
+            Some(Ok(Node::Open(element))) => {
+                let id = deserializer.element_id(&element)?;
+                let mut nested = deserializer.nested(element);
+                if M::Value::KIND == Kind::Element && M::Value::matches(id, None) {
+                    seen_value = true;
+                    M::Value::deserialize(&mut value, field, &mut nested)?;
+                }
+                nested.ignore()?;
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e),
+            None => break,
+        }
+    }
+
+    // Give scalar values a chance to handle empty content (e.g. an empty `String`)
+    if !seen_value && M::Value::KIND == Kind::Scalar {
+        M::Value::deserialize(&mut value, field, deserializer)?;
+    }
+
+    into.insert_unique(key_value.try_done(field)?, value.try_done(field)?, field)
+}
+
+/// Serialize map entries keyed by an attribute: `<entry key="k">v</entry>`
+pub fn serialize_keyed_entries<'a, K, V, W>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    key: &str,
+    field: Id<'_>,
+    serializer: &mut Serializer<'_, W>,
+) -> Result<(), Error>
+where
+    K: ToXml + 'a,
+    V: ToXml + 'a,
+    W: fmt::Write + ?Sized,
+{
+    for (k, v) in entries {
+        let element = serializer.write_start(field.name, field.ns, None::<Context<0>>)?;
+        serializer.write_attr(key, "", k)?;
+        serializer.end_start()?;
+        v.serialize(None, serializer)?;
+        serializer.write_close(element)?;
+    }
+
+    Ok(())
+}
+
+/// Deserialize a map entry with key and value child elements:
+/// `<entry><key>k</key><value>v</value></entry>`
+pub fn deserialize_pair_entry<'xml, M>(
+    into: &mut M,
+    key: &str,
+    value: &str,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, 'xml>,
+) -> Result<(), Error>
+where
+    M: Map,
+    M::Key: FromXml<'xml>,
+    M::Value: FromXml<'xml>,
+{
+    let ns = deserializer.parent().ns;
+    let mut key_value = <M::Key as FromXml<'xml>>::Accumulator::default();
+    let mut value_value = <M::Value as FromXml<'xml>>::Accumulator::default();
+    loop {
+        let element = match deserializer.next() {
+            Some(Ok(Node::Open(element))) => element,
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e),
+            None => break,
+        };
+
+        let id = deserializer.element_id(&element)?;
+        let mut nested = deserializer.nested(element);
+        if M::Key::matches(id, Some(Id { ns, name: key })) {
+            M::Key::deserialize(&mut key_value, field, &mut nested)?;
+        } else if M::Value::matches(id, Some(Id { ns, name: value })) {
+            M::Value::deserialize(&mut value_value, field, &mut nested)?;
+        }
+        nested.ignore()?;
+    }
+
+    into.insert_unique(
+        key_value.try_done(field)?,
+        value_value.try_done(field)?,
+        field,
+    )
+}
+
+/// Serialize map entries with key and value child elements:
+/// `<entry><key>k</key><value>v</value></entry>`
+pub fn serialize_pair_entries<'a, K, V, W>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    key: &str,
+    value: &str,
+    field: Id<'_>,
+    serializer: &mut Serializer<'_, W>,
+) -> Result<(), Error>
+where
+    K: ToXml + 'a,
+    V: ToXml + 'a,
+    W: fmt::Write + ?Sized,
+{
+    for (k, v) in entries {
+        let element = serializer.write_start(field.name, field.ns, None::<Context<0>>)?;
+        serializer.end_start()?;
+        k.serialize(
+            Some(Id {
+                ns: field.ns,
+                name: key,
+            }),
+            serializer,
+        )?;
+        v.serialize(
+            Some(Id {
+                ns: field.ns,
+                name: value,
+            }),
+            serializer,
+        )?;
+        serializer.write_close(element)?;
+    }
+
+    Ok(())
+}
+
+fn deserialize_named_entries<'xml, M>(
+    into: &mut M,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, 'xml>,
+) -> Result<(), Error>
+where
+    M: Map,
+    M::Key: FromXml<'xml>,
+    M::Value: FromXml<'xml>,
+{
+    if M::Value::KIND != Kind::Scalar {
+        return Err(Error::UnexpectedState(
+            "map values keyed by element name must be scalar values",
+        ));
+    }
+
+    let ns = deserializer.parent().ns;
+    loop {
+        let element = match deserializer.next() {
+            Some(Ok(Node::Open(element))) => element,
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e),
+            None => break,
+        };
+
+        let id = deserializer.element_id(&element)?;
+        if id.ns != ns {
+            let mut nested = deserializer.nested(element);
+            nested.ignore()?;
+            continue;
+        }
+
+        let mut key = <M::Key as FromXml<'xml>>::Accumulator::default();
+        let mut nested = deserializer.for_node(Node::Text(id.name.into()));
+        M::Key::deserialize(&mut key, field, &mut nested)?;
+
+        let mut value = <M::Value as FromXml<'xml>>::Accumulator::default();
+        let mut nested = deserializer.nested(element);
+        M::Value::deserialize(&mut value, field, &mut nested)?;
+        nested.ignore()?;
+
+        into.insert_unique(key.try_done(field)?, value.try_done(field)?, field)?;
+    }
+
+    Ok(())
+}
+
+fn serialize_named_entries<'a, K, V, W>(
+    entries: impl IntoIterator<Item = (&'a K, &'a V)>,
+    field: Option<Id<'_>>,
+    serializer: &mut Serializer<'_, W>,
+) -> Result<(), Error>
+where
+    K: ToXml + 'a,
+    V: ToXml + 'a,
+    W: fmt::Write + ?Sized,
+{
+    let (element, ns) = match field {
+        Some(id) => {
+            let element = serializer.write_start(id.name, id.ns, None::<Context<0>>)?;
+            serializer.end_start()?;
+            (Some(element), id.ns)
+        }
+        None => (None, serializer.default_ns()),
+    };
+
+    let mut name = String::new();
+    for (k, v) in entries {
+        name.clear();
+        k.serialize(None, &mut Serializer::new(&mut name))?;
+        if !is_ncname(&name) {
+            return Err(Error::UnexpectedValue(format!(
+                "map key `{name}` is not a valid element name"
+            )));
+        }
+
+        v.serialize(Some(Id { ns, name: &name }), serializer)?;
+    }
+
+    if let Some(element) = element {
+        serializer.write_close(element)?;
+    }
+
+    Ok(())
+}
+
+impl<'xml, K, V, S> FromXml<'xml> for HashMap<K, V, S>
+where
+    K: FromXml<'xml> + Eq + Hash,
+    V: FromXml<'xml>,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        deserialize_named_entries(into, field, deserializer)
+    }
+
+    type Accumulator = Self;
+    const KIND: Kind = Kind::Scalar;
+}
+
+impl<K: ToXml, V: ToXml, S> ToXml for HashMap<K, V, S> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        serialize_named_entries(self, field, serializer)
+    }
+}
+
+impl<'xml, K, V> FromXml<'xml> for BTreeMap<K, V>
+where
+    K: FromXml<'xml> + Ord,
+    V: FromXml<'xml>,
+{
+    #[inline]
+    fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+        match field {
+            Some(field) => id == field,
+            None => false,
+        }
+    }
+
+    fn deserialize<'cx>(
+        into: &mut Self::Accumulator,
+        field: &'static str,
+        deserializer: &mut Deserializer<'cx, 'xml>,
+    ) -> Result<(), Error> {
+        deserialize_named_entries(into, field, deserializer)
+    }
+
+    type Accumulator = Self;
+    const KIND: Kind = Kind::Scalar;
+}
+
+impl<K: ToXml, V: ToXml> ToXml for BTreeMap<K, V> {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        serialize_named_entries(self, field, serializer)
+    }
+}
+
+impl<K, V, S: Default> Accumulate<Self> for HashMap<K, V, S> {
+    fn try_done(self, _: &'static str) -> Result<Self, Error> {
+        Ok(self)
+    }
+}
+
+impl<K, V> Accumulate<Self> for BTreeMap<K, V> {
+    fn try_done(self, _: &'static str) -> Result<Self, Error> {
+        Ok(self)
+    }
+}
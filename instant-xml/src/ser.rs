@@ -6,6 +6,7 @@ use std::fmt::{self};
 use std::mem;
 
 use super::Error;
+use crate::impls::encode;
 use crate::ToXml;
 
 /// XML serializer for writing structured XML output
@@ -18,11 +19,22 @@ pub struct Serializer<'xml, W: fmt::Write + ?Sized> {
     /// update the set with the new prefix.
     prefixes: HashMap<&'static str, &'static str>,
     default_ns: &'static str,
+    /// Whether the default namespace in scope differs from `default_ns`.
+    ///
+    /// This happens if an element declared a namespace only known at runtime as the default, in
+    /// which case child elements always declare their own namespace.
+    ns_overridden: bool,
+    /// Saved `ns_overridden` values for open elements with a namespace context
+    parent_overridden: Vec<bool>,
     /// Default namespace for attributes.
     ///
     /// This might be different from the default namespace for child elements in case of
     /// `force_prefix`.
     attribute_ns: Option<&'static str>,
+    /// Whether the `field` passed to the outermost value names the root element
+    root_name: bool,
+    /// Whether `write_str()` escapes its input, set while writing a custom attribute value
+    escape: bool,
     state: State,
 }
 
@@ -33,7 +45,11 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
             output,
             prefixes: HashMap::new(),
             default_ns: "",
+            ns_overridden: false,
+            parent_overridden: Vec::new(),
             attribute_ns: None,
+            root_name: false,
+            escape: false,
             state: State::Element,
         }
     }
@@ -55,13 +71,32 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
         }
 
         let force_prefix = cx.as_ref().is_some_and(|cx| cx.force_prefix);
+        let is_default = !self.ns_overridden && self.default_ns == ns;
 
-        let (prefix, update_default_ns) =
-            match (ns == self.default_ns, self.prefixes.get(ns), force_prefix) {
+        // The element's own namespace differs from the default namespace for its children if
+        // its name was overridden at runtime. Its children then declare their own namespace.
+        let (prefix, update_default_ns) = match &cx {
+            Some(cx) if cx.default_ns != ns => match (is_default, self.prefixes.get(ns)) {
+                (true, _) => {
+                    self.output.write_fmt(format_args!("<{name}"))?;
+                    (None, Some((cx.default_ns, true)))
+                }
+                (false, Some(prefix)) => {
+                    self.output.write_fmt(format_args!("<{prefix}:{name}"))?;
+                    let overridden = self.ns_overridden || self.default_ns != cx.default_ns;
+                    (Some(*prefix), Some((cx.default_ns, overridden)))
+                }
+                (false, None) => {
+                    self.output
+                        .write_fmt(format_args!("<{name} xmlns=\"{ns}\""))?;
+                    (None, Some((cx.default_ns, true)))
+                }
+            },
+            _ => match (is_default, self.prefixes.get(ns), force_prefix) {
                 // Ns != default ns, force prefix: use prefix, don't change default ns
                 (false, Some(prefix), true) => {
                     self.output.write_fmt(format_args!("<{prefix}:{name}"))?;
-                    (Some(*prefix), false)
+                    (Some(*prefix), None)
                 }
                 // Ns != default ns, no force prefix: use prefix and set new default ns
                 (false, Some(prefix), false) => {
@@ -70,12 +105,12 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
                         self.output
                             .write_fmt(format_args!(" xmlns=\"{}\"", cx.default_ns))?;
                     }
-                    (Some(*prefix), true)
+                    (Some(*prefix), cx.as_ref().map(|cx| (cx.default_ns, false)))
                 }
                 // No prefix case
                 (true, None, _) => {
                     self.output.write_fmt(format_args!("<{name}"))?;
-                    (None, false)
+                    (None, None)
                 }
                 // If ns == default ns and force-prefix is false, we ignore prefix
                 (true, Some(_), false) => {
@@ -85,21 +120,23 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
                         self.output
                             .write_fmt(format_args!(" xmlns=\"{}\"", cx.default_ns))?;
                     }
-                    (None, true)
+                    (None, cx.as_ref().map(|cx| (cx.default_ns, false)))
                 }
                 // Force prefix always - when forcing prefix we dont requalify the namespace
                 (true, Some(prefix), true) => {
                     self.output.write_fmt(format_args!("<{prefix}:{name}"))?;
-                    (Some(*prefix), false)
+                    (Some(*prefix), None)
                 }
                 _ => {
                     self.output
                         .write_fmt(format_args!("<{name} xmlns=\"{ns}\""))?;
-                    (None, true)
+                    (None, cx.as_ref().map(|cx| (cx.default_ns, false)))
                 }
-            };
+            },
+        };
 
         self.state = State::Attribute;
+        self.root_name = false;
         self.attribute_ns = cx.as_ref().map(|cx| cx.default_ns);
         let Some(cx) = cx else {
             return Ok(Element {
@@ -109,12 +146,15 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
             });
         };
 
-        let mut old = Context::default();
-        let prev = match update_default_ns {
-            true => mem::replace(&mut self.default_ns, cx.default_ns),
-            false => self.default_ns,
+        let mut old = Context {
+            default_ns: self.default_ns,
+            ..Context::default()
         };
-        let _ = mem::replace(&mut old.default_ns, prev);
+        self.parent_overridden.push(self.ns_overridden);
+        if let Some((default_ns, overridden)) = update_default_ns {
+            self.default_ns = default_ns;
+            self.ns_overridden = overridden;
+        }
 
         let mut used = 0;
         for prefix in cx.prefixes.into_iter() {
@@ -157,13 +197,39 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
         name: &str,
         ns: &str,
         value: &V,
+    ) -> Result<(), Error> {
+        self.write_attr_value(name, ns, false, |serializer| {
+            value.serialize(None, serializer)
+        })
+    }
+
+    /// Write an attribute with the given name, using a function to write the value
+    ///
+    /// The function should write the raw value using [`Serializer::write_str()`], which escapes
+    /// it for use in an attribute. Values serialized through [`ToXml`] are already escaped, so
+    /// use [`Serializer::write_attr()`] for those instead.
+    pub fn write_attr_with(
+        &mut self,
+        name: &str,
+        ns: &str,
+        value: impl FnOnce(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.write_attr_value(name, ns, true, value)
+    }
+
+    fn write_attr_value(
+        &mut self,
+        name: &str,
+        ns: &str,
+        escape: bool,
+        value: impl FnOnce(&mut Self) -> Result<(), Error>,
     ) -> Result<(), Error> {
         if self.state != State::Attribute {
             return Err(Error::UnexpectedState("invalid state for attribute"));
         }
 
         let attr_ns = self.attribute_ns.unwrap_or(self.default_ns);
-        match ns.is_empty() || ns == attr_ns {
+        match ns.is_empty() || attr_ns == ns {
             true => self.output.write_fmt(format_args!(" {name}=\""))?,
             false => {
                 let prefix = self
@@ -175,7 +241,10 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
         }
 
         self.state = State::Scalar;
-        value.serialize(None, self)?;
+        self.escape = escape;
+        let result = value(self);
+        self.escape = false;
+        result?;
         self.state = State::Attribute;
         self.output.write_char('"')?;
         Ok(())
@@ -187,7 +256,10 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
             return Err(Error::UnexpectedState("invalid state for scalar"));
         }
 
-        self.output.write_fmt(format_args!("{value}"))?;
+        match self.escape {
+            true => self.output.write_str(&encode(&value.to_string())?)?,
+            false => self.output.write_fmt(format_args!("{value}"))?,
+        }
         self.state = State::Element;
         Ok(())
     }
@@ -233,7 +305,8 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
             return Ok(());
         };
 
-        let _ = mem::replace(&mut self.default_ns, old.default_ns);
+        self.default_ns = old.default_ns;
+        self.ns_overridden = self.parent_overridden.pop().unwrap_or_default();
         for prefix in old.prefixes.into_iter() {
             if prefix.ns.is_empty() && prefix.prefix.is_empty() {
                 continue;
@@ -257,6 +330,20 @@ impl<'xml, W: fmt::Write + ?Sized> Serializer<'xml, W> {
         Ok(())
     }
 
+    /// Treat the `field` passed to the outermost value as the name of the root element
+    ///
+    /// Derived implementations normally ignore `field` in favor of their own name. This is
+    /// used by [`to_string_as()`](crate::to_string_as).
+    pub fn with_root_name(mut self) -> Self {
+        self.root_name = true;
+        self
+    }
+
+    /// Whether the `field` passed in names the root element, which has not been written yet
+    pub fn root_name(&self) -> bool {
+        self.root_name
+    }
+
     /// Get the prefix for a namespace URI, if any
     pub fn prefix(&self, ns: &str) -> Option<&'static str> {
         self.prefixes.get(ns).copied()
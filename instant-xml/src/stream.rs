@@ -0,0 +1,427 @@
+//! Incremental parsing of XML streams with a long-lived root element
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::str;
+
+use crate::de::{deserialize_child, open_element, Level, Limits};
+use crate::{AnyAttribute, Error, FromXmlOwned};
+
+/// Push-based parser for streams whose root element stays open, as in XMPP
+///
+/// Input is pushed in chunks as it arrives, after which [`next_event()`](Self::next_event)
+/// reports the opening of the root element, each complete child element deserialized as `T`,
+/// and finally the closing of the root element. Namespaces declared on the root element stay
+/// in scope for its children.
+///
+/// ```
+/// use instant_xml::{FromXml, StreamEvent, StreamParser};
+///
+/// #[derive(Debug, FromXml, PartialEq)]
+/// #[xml(rename = "message", ns("jabber:client"))]
+/// struct Message {
+///     body: String,
+/// }
+///
+/// let mut parser = StreamParser::<Message>::new();
+/// parser.push(b"<stream:stream xmlns='jabber:client' ").unwrap();
+/// assert!(parser.next_event().is_none());
+///
+/// parser.push(b"xmlns:stream='http://etherx.jabber.org/streams'><message><bo").unwrap();
+/// match parser.next_event() {
+///     Some(Ok(StreamEvent::Open(header))) => assert_eq!(header.name, "stream"),
+///     event => panic!("unexpected event: {event:?}"),
+/// }
+/// assert!(parser.next_event().is_none());
+///
+/// parser.push(b"dy>hi</body></message></stream:stream>").unwrap();
+/// let message = Message { body: "hi".to_owned() };
+/// assert_eq!(parser.next_event().unwrap().unwrap(), StreamEvent::Stanza(message));
+/// assert_eq!(parser.next_event().unwrap().unwrap(), StreamEvent::Close);
+/// ```
+pub struct StreamParser<T> {
+    /// Input that has not been consumed yet
+    buffer: String,
+    /// Incomplete UTF-8 sequence at the end of the last chunk
+    partial: Vec<u8>,
+    /// Whether invalid UTF-8 follows the input in `buffer`
+    invalid: bool,
+    /// Scan position in `buffer`
+    pos: usize,
+    /// Nesting depth of the current child element, if one is being scanned
+    depth: usize,
+    root: Option<Root>,
+    state: State,
+    limits: Limits,
+    /// Maximum size in bytes of the root element's opening tag and of each child element
+    max_stanza_size: usize,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T: FromXmlOwned> StreamParser<T> {
+    /// Create a parser waiting for the root element
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            partial: Vec::new(),
+            invalid: false,
+            pos: 0,
+            depth: 0,
+            root: None,
+            state: State::Prolog,
+            limits: Limits::default(),
+            max_stanza_size: 1 << 20,
+            value: PhantomData,
+        }
+    }
+
+    /// Override default limits, which apply to the root element and each child element
+    ///
+    /// The nesting limit is also enforced while child elements are still incomplete.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Override the maximum size in bytes of the root element's opening tag and of each child
+    /// element, which defaults to 1 MiB
+    ///
+    /// Input exceeding it ends the stream with an error, even before it is complete.
+    pub fn with_max_stanza_size(mut self, max: usize) -> Self {
+        self.max_stanza_size = max;
+        self
+    }
+
+    /// Append a chunk of input
+    ///
+    /// Chunks may split the input anywhere, including inside multi-byte characters. Invalid
+    /// UTF-8 ends the stream: events for the input before it are still returned, after which
+    /// [`next_event()`](Self::next_event) reports the error. Later input is rejected.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), Error> {
+        if self.invalid {
+            return Err(invalid_utf8());
+        }
+
+        self.partial.extend_from_slice(chunk);
+        let valid = match str::from_utf8(&self.partial) {
+            Ok(text) => text.len(),
+            Err(err) => {
+                self.invalid = err.error_len().is_some();
+                err.valid_up_to()
+            }
+        };
+
+        if let Ok(text) = str::from_utf8(&self.partial[..valid]) {
+            self.buffer.push_str(text);
+        }
+
+        match self.invalid {
+            true => {
+                self.partial.clear();
+                Err(invalid_utf8())
+            }
+            false => {
+                self.partial.drain(..valid);
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the next event from the input pushed so far
+    ///
+    /// Returns `None` if more input is needed, or once the root element has been closed. Errors
+    /// in a child element are reported without ending the stream, while errors in the structure
+    /// of the stream itself end it.
+    pub fn next_event(&mut self) -> Option<Result<StreamEvent<T>, Error>> {
+        match self.advance() {
+            Ok(event) => {
+                if let Some(Ok(StreamEvent::Close)) = &event {
+                    self.state = State::Closed;
+                }
+                event
+            }
+            Err(err) => {
+                self.state = State::Closed;
+                Some(Err(err))
+            }
+        }
+    }
+
+    /// The opening tag of the root element, once it has been parsed
+    pub fn header(&self) -> Option<&StreamHeader> {
+        self.root.as_ref().map(|root| &root.header)
+    }
+
+    /// Scan for the next event, failing only on errors in the structure of the stream
+    fn advance(&mut self) -> Result<Option<Result<StreamEvent<T>, Error>>, Error> {
+        loop {
+            if self.state == State::Closed {
+                return Ok(None);
+            } else if self.state == State::Empty {
+                return Ok(Some(Ok(StreamEvent::Close)));
+            }
+
+            // The buffer starts at the stanza or markup being scanned
+            let start = self.pos;
+            let Some((markup, end)) = self.scan()? else {
+                if self.invalid {
+                    return Err(invalid_utf8());
+                } else if self.buffer.len() > self.max_stanza_size {
+                    return Err(Error::Other("maximum stanza size exceeded".to_owned()));
+                }
+                return Ok(None);
+            };
+
+            if end > self.max_stanza_size {
+                return Err(Error::Other("maximum stanza size exceeded".to_owned()));
+            }
+
+            self.pos = end;
+            match (self.state, markup, self.depth) {
+                (_, Markup::Misc, _) | (_, Markup::Text, 1..) => {}
+                (_, Markup::Text, 0) => {
+                    let text = &self.buffer[start..end];
+                    if !text.trim().is_empty() {
+                        return Err(Error::UnexpectedNode(format!(
+                            "text {text:?} outside of child elements"
+                        )));
+                    }
+                }
+                (State::Prolog, Markup::Start { empty }, _) => {
+                    let root = Root::new(&self.buffer, start..end, self.limits)?;
+                    let header = root.header.clone();
+                    self.root = Some(root);
+                    self.state = match empty {
+                        true => State::Empty,
+                        false => State::Open,
+                    };
+
+                    self.consume();
+                    return Ok(Some(Ok(StreamEvent::Open(header))));
+                }
+                (State::Prolog, Markup::End, _) => {
+                    return Err(Error::UnexpectedState("closing element without parent"))
+                }
+                (_, Markup::Start { empty: true }, 0) => {
+                    let stanza = self.stanza(start..end);
+                    self.consume();
+                    return Ok(Some(stanza.map(StreamEvent::Stanza)));
+                }
+                (_, Markup::Start { empty: false }, 0) => {
+                    self.buffer.drain(..start);
+                    self.pos -= start;
+                    self.depth = 1;
+                }
+                (_, Markup::Start { empty }, _) => {
+                    // The root element and the open child elements are on the stack
+                    if self.depth + 1 >= self.limits.max_levels {
+                        return Err(Error::Other(
+                            "maximum number of nested element levels exceeded".to_owned(),
+                        ));
+                    }
+
+                    self.depth += usize::from(!empty);
+                }
+                (_, Markup::End, 0) => {
+                    let close = self.buffer[start + 2..end - 1].trim_end();
+                    return match self.root.as_ref().is_some_and(|root| root.name == close) {
+                        true => Ok(Some(Ok(StreamEvent::Close))),
+                        false => Err(Error::UnexpectedState("close element mismatch")),
+                    };
+                }
+                (_, Markup::End, 1) => {
+                    self.depth = 0;
+                    let stanza = self.stanza(0..end);
+                    self.consume();
+                    return Ok(Some(stanza.map(StreamEvent::Stanza)));
+                }
+                (_, Markup::End, _) => self.depth -= 1,
+            }
+
+            if self.depth == 0 {
+                self.consume();
+            }
+        }
+    }
+
+    /// Deserialize the complete child element in `buffer[range]`
+    fn stanza(&self, range: Range<usize>) -> Result<T, Error> {
+        let Some(root) = &self.root else {
+            return Err(Error::UnexpectedState("child element without root"));
+        };
+
+        deserialize_child(&self.buffer, range, root.level(), self.limits)
+    }
+
+    /// Drop the input up to the scan position
+    fn consume(&mut self) {
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+    }
+
+    /// Find the end of the markup or text starting at the scan position
+    ///
+    /// Returns `None` if it is incomplete.
+    fn scan(&self) -> Result<Option<(Markup, usize)>, Error> {
+        let rest = &self.buffer[self.pos..];
+        let found = |markup, pattern: &str| {
+            rest.find(pattern)
+                .map(|i| (markup, self.pos + i + pattern.len()))
+        };
+
+        Ok(if rest.is_empty() {
+            None
+        } else if !rest.starts_with('<') {
+            match rest.find('<') {
+                Some(i) => Some((Markup::Text, self.pos + i)),
+                // Whitespace between child elements need not wait for the next element
+                None if self.depth == 0 => Some((Markup::Text, self.buffer.len())),
+                None => None,
+            }
+        } else if rest.starts_with("<!--") {
+            found(Markup::Misc, "-->")
+        } else if rest.starts_with("<![CDATA[") {
+            found(Markup::Text, "]]>")
+        } else if "<!--".starts_with(rest) || "<![CDATA[".starts_with(rest) {
+            None
+        } else if rest.starts_with("<?") {
+            found(Markup::Misc, "?>")
+        } else if rest.starts_with("<!") {
+            found(Markup::Misc, ">")
+        } else if rest.starts_with("</") {
+            found(Markup::End, ">")
+        } else {
+            // Skip over quoted attribute values, which may contain `>`
+            let mut quote = None;
+            let end = rest.char_indices().find(|&(_, c)| match quote {
+                Some(q) => {
+                    if c == q {
+                        quote = None;
+                    }
+                    false
+                }
+                None if c == '"' || c == '\'' => {
+                    quote = Some(c);
+                    false
+                }
+                None => c == '>',
+            });
+
+            end.map(|(i, _)| {
+                let empty = rest[..i].ends_with('/');
+                (Markup::Start { empty }, self.pos + i + 1)
+            })
+        })
+    }
+}
+
+fn invalid_utf8() -> Error {
+    Error::Other("invalid UTF-8 in stream".to_owned())
+}
+
+impl<T: FromXmlOwned> Default for StreamParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An event from a [`StreamParser`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamEvent<T> {
+    /// The root element was opened
+    Open(StreamHeader),
+    /// A complete child element of the root element
+    Stanza(T),
+    /// The root element was closed
+    Close,
+}
+
+/// The opening tag of a stream's root element
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamHeader {
+    /// Namespace URI of the root element
+    pub ns: String,
+    /// Local name of the root element
+    pub name: String,
+    /// Attributes of the root element, other than namespace declarations
+    pub attributes: Vec<AnyAttribute<'static>>,
+    /// Namespace declarations on the root element by prefix, with an empty prefix for the
+    /// default namespace
+    pub namespaces: BTreeMap<String, String>,
+}
+
+/// The root element, which provides the namespace scope for child elements
+struct Root {
+    header: StreamHeader,
+    /// Qualified name, to match the closing tag
+    name: String,
+    prefix: Option<String>,
+}
+
+impl Root {
+    fn new(input: &str, range: Range<usize>, limits: Limits) -> Result<Self, Error> {
+        let (id, attributes, level) = open_element(input, range, limits)?;
+        let mut namespaces = BTreeMap::new();
+        if let Some(ns) = level.default_ns {
+            namespaces.insert(String::new(), ns.to_owned());
+        }
+
+        for (prefix, ns) in level.prefixes {
+            namespaces.insert(prefix.to_owned(), ns.to_owned());
+        }
+
+        Ok(Self {
+            header: StreamHeader {
+                ns: id.ns.to_owned(),
+                name: id.name.to_owned(),
+                attributes: attributes
+                    .into_iter()
+                    .map(AnyAttribute::into_owned)
+                    .collect(),
+                namespaces,
+            },
+            name: match level.prefix {
+                Some(prefix) => format!("{prefix}:{}", level.local),
+                None => level.local.to_owned(),
+            },
+            prefix: level.prefix.map(str::to_owned),
+        })
+    }
+
+    fn level(&self) -> Level<'_> {
+        let namespaces = &self.header.namespaces;
+        Level {
+            local: &self.header.name,
+            prefix: self.prefix.as_deref(),
+            default_ns: namespaces.get("").map(String::as_str),
+            prefixes: namespaces
+                .iter()
+                .filter(|(prefix, _)| !prefix.is_empty())
+                .map(|(prefix, ns)| (prefix.as_str(), ns.as_str()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    /// Waiting for the root element
+    Prolog,
+    /// The root element is open
+    Open,
+    /// The root element was empty, so it is closed right away
+    Empty,
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Markup {
+    Start {
+        empty: bool,
+    },
+    End,
+    Text,
+    /// Comments, processing instructions and declarations
+    Misc,
+}
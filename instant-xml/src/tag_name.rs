@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use crate::Id;
+
+/// Types holding an element's name, for fields with the `tag_name` attribute
+///
+/// Deserialization stores the name of the element being deserialized in the field, and
+/// serialization writes the element using the field's name instead of the static tag.
+pub trait TagName<'xml> {
+    /// Create a value from the identifier of the deserialized element
+    fn from_id(id: Id<'xml>) -> Self;
+
+    /// The local name to serialize the element with
+    fn name(&self) -> &str;
+
+    /// The namespace to serialize the element with
+    ///
+    /// If `None`, the namespace of the container type is used.
+    fn ns(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<'xml> TagName<'xml> for String {
+    fn from_id(id: Id<'xml>) -> Self {
+        id.name.to_owned()
+    }
+
+    fn name(&self) -> &str {
+        self
+    }
+}
+
+impl<'xml: 'a, 'a> TagName<'xml> for Cow<'a, str> {
+    fn from_id(id: Id<'xml>) -> Self {
+        Cow::Borrowed(id.name)
+    }
+
+    fn name(&self) -> &str {
+        self
+    }
+}
+
+impl<'xml: 'a, 'a> TagName<'xml> for Id<'a> {
+    fn from_id(id: Id<'xml>) -> Self {
+        id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn ns(&self) -> Option<&str> {
+        Some(self.ns)
+    }
+}
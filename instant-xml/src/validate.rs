@@ -0,0 +1,288 @@
+//! Support code for field validation facets
+//!
+//! These helpers are called by the code generated for the `min_len`, `max_len`, `pattern`,
+//! `min_occurs`, `max_occurs` and `tag_name` field attributes.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+#[cfg(feature = "regex")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+
+use crate::xsd::is_ncname;
+use crate::Error;
+
+/// Check that a value has at least `min` characters or items
+pub fn min_len<T: Length + ?Sized>(
+    value: &T,
+    min: usize,
+    field: &'static str,
+) -> Result<(), Error> {
+    match value.length() {
+        Some(len) if len < min => Err(Error::Validation {
+            field,
+            message: format!("length {len} is less than the minimum of {min}"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a value has at most `max` characters or items
+pub fn max_len<T: Length + ?Sized>(
+    value: &T,
+    max: usize,
+    field: &'static str,
+) -> Result<(), Error> {
+    match value.length() {
+        Some(len) if len > max => Err(Error::Validation {
+            field,
+            message: format!("length {len} exceeds the maximum of {max}"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a repeated field occurs at least `min` times
+pub fn min_occurs<T: Occurs + ?Sized>(
+    value: &T,
+    min: usize,
+    field: &'static str,
+) -> Result<(), Error> {
+    match value.count() {
+        Some(count) if count < min => Err(Error::TooFewOccurrences { field, count, min }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a repeated field occurs at most `max` times
+pub fn max_occurs<T: Occurs + ?Sized>(
+    value: &T,
+    max: usize,
+    field: &'static str,
+) -> Result<(), Error> {
+    match value.count() {
+        Some(count) if count > max => Err(Error::TooManyOccurrences { field, count, max }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the value of a `tag_name` field is a valid element name
+pub fn tag_name<'a>(name: &'a str, field: &'static str) -> Result<&'a str, Error> {
+    match is_ncname(name) {
+        true => Ok(name),
+        false => Err(Error::UnexpectedValue(format!(
+            "tag name `{name}` for {field} is not a valid element name"
+        ))),
+    }
+}
+
+/// Check that the entire text of a value matches the regular expression `pattern`
+///
+/// As in XML Schema, the pattern is implicitly anchored at both ends. The compiled expression
+/// is cached in `regex`.
+#[cfg(feature = "regex")]
+pub fn pattern<T: Text + ?Sized>(
+    value: &T,
+    regex: &OnceLock<Regex>,
+    pattern: &str,
+    field: &'static str,
+) -> Result<(), Error> {
+    let Some(text) = value.text() else {
+        return Ok(());
+    };
+
+    let regex = match regex.get() {
+        Some(regex) => regex,
+        None => {
+            let compiled = Regex::new(&format!("^(?:{pattern})$"))
+                .map_err(|err| Error::Other(format!("invalid pattern for {field}: {err}")))?;
+            regex.get_or_init(|| compiled)
+        }
+    };
+
+    match regex.is_match(text) {
+        true => Ok(()),
+        false => Err(Error::Validation {
+            field,
+            message: format!("`{text}` does not match pattern `{pattern}`"),
+        }),
+    }
+}
+
+/// Values with a length, used for the `min_len` and `max_len` facets
+///
+/// Text is measured in characters, collections in items. Absent optional values have no length
+/// and always pass validation.
+pub trait Length {
+    /// The length of the value, if present
+    fn length(&self) -> Option<usize>;
+}
+
+impl Length for str {
+    fn length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> Option<usize> {
+        self.as_str().length()
+    }
+}
+
+impl Length for Cow<'_, str> {
+    fn length(&self) -> Option<usize> {
+        self.as_ref().length()
+    }
+}
+
+impl<T: Length> Length for Option<T> {
+    fn length(&self) -> Option<usize> {
+        self.as_ref().and_then(Length::length)
+    }
+}
+
+impl<T: Length + ?Sized> Length for Box<T> {
+    fn length(&self) -> Option<usize> {
+        (**self).length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> Length for VecDeque<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> Length for BTreeSet<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T, S> Length for HashSet<T, S> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V, S> Length for HashMap<K, V, S> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+/// Repeated element fields, used for the `min_occurs` and `max_occurs` facets
+///
+/// Only implemented for collections, so that the facets are rejected at compile time on fields
+/// that hold a single element. Absent optional collections always pass validation.
+///
+/// ```compile_fail
+/// #[derive(instant_xml::FromXml)]
+/// struct Page {
+///     #[xml(max_occurs = 2)]
+///     title: String,
+/// }
+/// ```
+pub trait Occurs {
+    /// The number of occurrences, if present
+    fn count(&self) -> Option<usize>;
+}
+
+impl<T: Occurs> Occurs for Option<T> {
+    fn count(&self) -> Option<usize> {
+        self.as_ref().and_then(Occurs::count)
+    }
+}
+
+impl<T> Occurs for Vec<T> {
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> Occurs for VecDeque<T> {
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> Occurs for BTreeSet<T> {
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T, S> Occurs for HashSet<T, S> {
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V> Occurs for BTreeMap<K, V> {
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V, S> Occurs for HashMap<K, V, S> {
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+/// Textual values, used for the `pattern` facet
+pub trait Text {
+    /// The text of the value, if present
+    fn text(&self) -> Option<&str>;
+}
+
+impl Text for str {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl Text for String {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl Text for Cow<'_, str> {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: Text> Text for Option<T> {
+    fn text(&self) -> Option<&str> {
+        self.as_ref().and_then(Text::text)
+    }
+}
+
+impl<T: Text + ?Sized> Text for Box<T> {
+    fn text(&self) -> Option<&str> {
+        (**self).text()
+    }
+}
@@ -0,0 +1,936 @@
+//! XML Schema built-in datatypes
+//!
+//! Newtypes for common [XML Schema datatypes](https://www.w3.org/TR/xmlschema11-2/). Values are
+//! validated against the type's lexical space when deserializing (or parsing with [`FromStr`]),
+//! and serialized (or formatted with [`Display`](fmt::Display)) using the canonical
+//! representation.
+//!
+//! ```
+//! use instant_xml::xsd::{Decimal, Duration, NCName};
+//! use instant_xml::{from_str, to_string, FromXml, ToXml};
+//!
+//! #[derive(Debug, FromXml, ToXml)]
+//! struct Offer {
+//!     #[xml(attribute)]
+//!     id: NCName,
+//!     price: Decimal,
+//!     validity: Duration,
+//! }
+//!
+//! let offer = from_str::<Offer>(
+//!     r#"<Offer id="o1"><price>+010.50</price><validity>P0Y14M</validity></Offer>"#,
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     to_string(&offer).unwrap(),
+//!     r#"<Offer id="o1"><price>10.5</price><validity>P1Y2M</validity></Offer>"#
+//! );
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::binary::{decode_base64, decode_hex, encode_base64, encode_hex};
+use crate::{
+    display_to_xml, from_xml_str, Deserializer, Error, FromXml, Id, Kind, Serializer, ToXml,
+};
+
+macro_rules! xsd_scalar {
+    ($ty:ty) => {
+        impl<'xml> FromXml<'xml> for $ty {
+            #[inline]
+            fn matches(id: Id<'_>, field: Option<Id<'_>>) -> bool {
+                match field {
+                    Some(field) => id == field,
+                    None => false,
+                }
+            }
+
+            fn deserialize<'cx>(
+                into: &mut Self::Accumulator,
+                field: &'static str,
+                deserializer: &mut Deserializer<'cx, 'xml>,
+            ) -> Result<(), Error> {
+                from_xml_str(into, field, deserializer)
+            }
+
+            type Accumulator = Option<Self>;
+            const KIND: Kind = Kind::Scalar;
+        }
+    };
+}
+
+macro_rules! xsd_display {
+    ($ty:ty) => {
+        impl ToXml for $ty {
+            fn serialize<W: fmt::Write + ?Sized>(
+                &self,
+                field: Option<Id<'_>>,
+                serializer: &mut Serializer<'_, W>,
+            ) -> Result<(), Error> {
+                display_to_xml(self, field, serializer)
+            }
+        }
+    };
+}
+
+/// `xs:token`: a string without line breaks, tabs or consecutive spaces
+///
+/// Parsing collapses whitespace as required by the datatype's `whiteSpace` facet.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Token(String);
+
+impl Token {
+    /// Create a token from the given string, collapsing whitespace
+    pub fn new(value: &str) -> Self {
+        Self(collapse(value))
+    }
+
+    /// The token as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Token {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Token {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl ToXml for Token {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        self.0.serialize(field, serializer)
+    }
+}
+
+xsd_scalar!(Token);
+
+/// `xs:NCName`: an XML name without colons
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NCName(String);
+
+impl NCName {
+    /// Validate the given string as an `NCName`
+    pub fn new(value: &str) -> Result<Self, Error> {
+        match is_ncname(value) {
+            true => Ok(Self(value.to_owned())),
+            false => Err(invalid("NCName", value)),
+        }
+    }
+
+    /// The name as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for NCName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(trim(s))
+    }
+}
+
+impl fmt::Display for NCName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for NCName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+xsd_scalar!(NCName);
+xsd_display!(NCName);
+
+/// `xs:anyURI`: a URI reference
+///
+/// Any string is in the lexical space of `xs:anyURI` (as of XML Schema 1.1); parsing collapses
+/// whitespace. No URI syntax validation is performed.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AnyUri(String);
+
+impl AnyUri {
+    /// Create a URI from the given string, collapsing whitespace
+    pub fn new(value: &str) -> Self {
+        Self(collapse(value))
+    }
+
+    /// The URI as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for AnyUri {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl fmt::Display for AnyUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for AnyUri {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl ToXml for AnyUri {
+    fn serialize<W: fmt::Write + ?Sized>(
+        &self,
+        field: Option<Id<'_>>,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        self.0.serialize(field, serializer)
+    }
+}
+
+xsd_scalar!(AnyUri);
+
+/// `xs:hexBinary`: binary data encoded as pairs of hexadecimal digits
+///
+/// The canonical representation uses upper case digits.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HexBinary(pub Vec<u8>);
+
+impl FromStr for HexBinary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match decode_hex(trim(s)) {
+            Some(bytes) => Ok(Self(bytes)),
+            None => Err(invalid("hexBinary", s)),
+        }
+    }
+}
+
+impl fmt::Display for HexBinary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_hex(&self.0, f)
+    }
+}
+
+impl From<Vec<u8>> for HexBinary {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+xsd_scalar!(HexBinary);
+xsd_display!(HexBinary);
+
+/// `xs:base64Binary`: binary data encoded as base64
+///
+/// Parsing ignores whitespace between characters. The canonical representation has no
+/// whitespace.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Base64Binary(pub Vec<u8>);
+
+impl FromStr for Base64Binary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match decode_base64(s) {
+            Some(bytes) => Ok(Self(bytes)),
+            None => Err(invalid("base64Binary", s)),
+        }
+    }
+}
+
+impl fmt::Display for Base64Binary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        encode_base64(&self.0, f)
+    }
+}
+
+impl From<Vec<u8>> for Base64Binary {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+xsd_scalar!(Base64Binary);
+xsd_display!(Base64Binary);
+
+/// `xs:decimal`: an arbitrary precision decimal number
+///
+/// Values are stored in their canonical representation: no leading `+`, no superfluous leading
+/// or trailing zeros, and no decimal point for integral values (`"-1.50"` becomes `"-1.5"`,
+/// `"+3.0"` becomes `"3"`). Equality compares values.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Decimal(String);
+
+impl Decimal {
+    /// The canonical representation as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((negative, int, frac)) = split_decimal(trim(s)) else {
+            return Err(invalid("decimal", s));
+        };
+
+        let (int, frac) = (int.trim_start_matches('0'), frac.trim_end_matches('0'));
+        let mut canonical = String::with_capacity(int.len() + frac.len() + 3);
+        if negative && !(int.is_empty() && frac.is_empty()) {
+            canonical.push('-');
+        }
+
+        canonical.push_str(if int.is_empty() { "0" } else { int });
+        if !frac.is_empty() {
+            canonical.push('.');
+            canonical.push_str(frac);
+        }
+
+        Ok(Self(canonical))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Self {
+        Self(value.to_string())
+    }
+}
+
+xsd_scalar!(Decimal);
+xsd_display!(Decimal);
+
+/// `xs:duration`: a duration in months and seconds
+///
+/// XML Schema durations consist of a month component and a seconds component that cannot be
+/// converted into each other. The canonical representation normalizes months into years and
+/// seconds into days, hours and minutes (`"PT90M"` becomes `"PT1H30M"`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Duration {
+    negative: bool,
+    months: u64,
+    seconds: u64,
+    nanos: u32,
+}
+
+impl Duration {
+    /// Create a new duration, normalizing nanoseconds into seconds
+    pub fn new(negative: bool, months: u64, seconds: u64, nanos: u32) -> Result<Self, Error> {
+        let seconds = seconds
+            .checked_add(u64::from(nanos / NANOS_PER_SECOND))
+            .ok_or_else(|| Error::UnexpectedValue("duration out of range".to_owned()))?;
+        let nanos = nanos % NANOS_PER_SECOND;
+        Ok(Self {
+            negative: negative && (months != 0 || seconds != 0 || nanos != 0),
+            months,
+            seconds,
+            nanos,
+        })
+    }
+
+    /// Whether the duration is negative
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The month component (years count as 12 months)
+    pub fn months(&self) -> u64 {
+        self.months
+    }
+
+    /// The whole seconds of the seconds component (days, hours and minutes included)
+    pub fn seconds(&self) -> u64 {
+        self.seconds
+    }
+
+    /// The fractional part of the seconds component, in nanoseconds
+    pub fn nanos(&self) -> u32 {
+        self.nanos
+    }
+}
+
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_duration(trim(s)).ok_or_else(|| invalid("duration", s))
+    }
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+
+    let value = value.strip_prefix('P')?;
+    let (date, time) = match value.split_once('T') {
+        Some((_, "")) => return None,
+        Some((date, time)) => (date, time),
+        None => (value, ""),
+    };
+
+    let (mut months, mut seconds, mut nanos) = (0u64, 0u64, 0);
+    let mut empty = true;
+    for (part, designators) in [(date, "YMD"), (time, "HMS")] {
+        let mut rest = part;
+        let mut allowed = designators;
+        while !rest.is_empty() {
+            empty = false;
+            let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+            let (number, designator) = (&rest[..end], rest[end..].chars().next()?);
+            rest = &rest[end + designator.len_utf8()..];
+
+            // Designators must appear in order, at most once each
+            allowed = &allowed[allowed.find(designator)? + 1..];
+            let (whole, frac) = match number.split_once('.') {
+                Some((whole, frac)) if designators == "HMS" && designator == 'S' => {
+                    (whole, Some(frac))
+                }
+                Some(_) => return None,
+                None => (number, None),
+            };
+
+            if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            let whole = whole.parse::<u64>().ok()?;
+            let (unit, target) = match (designators, designator) {
+                ("YMD", 'Y') => (12, &mut months),
+                ("YMD", 'M') => (1, &mut months),
+                ("YMD", 'D') => (86_400, &mut seconds),
+                (_, 'H') => (3_600, &mut seconds),
+                (_, 'M') => (60, &mut seconds),
+                (_, 'S') => (1, &mut seconds),
+                _ => return None,
+            };
+
+            *target = target.checked_add(whole.checked_mul(unit)?)?;
+            if let Some(frac) = frac {
+                nanos = parse_nanos(frac)?;
+            }
+        }
+    }
+
+    match empty {
+        true => None,
+        false => Duration::new(negative, months, seconds, nanos).ok(),
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+
+        f.write_str("P")?;
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            write!(f, "{years}Y")?;
+        }
+        if months != 0 {
+            write!(f, "{months}M")?;
+        }
+
+        let days = self.seconds / 86_400;
+        let hours = self.seconds % 86_400 / 3_600;
+        let minutes = self.seconds % 3_600 / 60;
+        let seconds = self.seconds % 60;
+        if days != 0 {
+            write!(f, "{days}D")?;
+        }
+
+        if hours == 0 && minutes == 0 && seconds == 0 && self.nanos == 0 {
+            return match self.months == 0 && days == 0 {
+                true => f.write_str("T0S"),
+                false => Ok(()),
+            };
+        }
+
+        f.write_str("T")?;
+        if hours != 0 {
+            write!(f, "{hours}H")?;
+        }
+        if minutes != 0 {
+            write!(f, "{minutes}M")?;
+        }
+        if seconds != 0 || self.nanos != 0 {
+            write!(f, "{seconds}")?;
+            write_nanos(self.nanos, f)?;
+            f.write_str("S")?;
+        }
+
+        Ok(())
+    }
+}
+
+xsd_scalar!(Duration);
+xsd_display!(Duration);
+
+/// `xs:gYear`: a calendar year with an optional timezone offset
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GYear {
+    year: i32,
+    offset: Option<i16>,
+}
+
+impl GYear {
+    /// Create a new year with an optional timezone offset in minutes
+    pub fn new(year: i32, offset: Option<i16>) -> Result<Self, Error> {
+        check_offset(offset)?;
+        Ok(Self { year, offset })
+    }
+
+    /// The year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The timezone offset in minutes, if any
+    pub fn offset(&self) -> Option<i16> {
+        self.offset
+    }
+}
+
+impl FromStr for GYear {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = trim(s);
+        let parsed = parse_year(value).and_then(|(year, rest)| {
+            let offset = parse_offset(rest)?;
+            Some(Self { year, offset })
+        });
+
+        parsed.ok_or_else(|| invalid("gYear", s))
+    }
+}
+
+impl fmt::Display for GYear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_year(self.year, f)?;
+        write_offset(self.offset, f)
+    }
+}
+
+xsd_scalar!(GYear);
+xsd_display!(GYear);
+
+/// `xs:gYearMonth`: a calendar month in a specific year with an optional timezone offset
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GYearMonth {
+    year: i32,
+    month: u8,
+    offset: Option<i16>,
+}
+
+impl GYearMonth {
+    /// Create a new year and month with an optional timezone offset in minutes
+    pub fn new(year: i32, month: u8, offset: Option<i16>) -> Result<Self, Error> {
+        check_offset(offset)?;
+        if !(1..=12).contains(&month) {
+            return Err(Error::UnexpectedValue(format!("invalid month {month}")));
+        }
+
+        Ok(Self {
+            year,
+            month,
+            offset,
+        })
+    }
+
+    /// The year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month (1-12)
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The timezone offset in minutes, if any
+    pub fn offset(&self) -> Option<i16> {
+        self.offset
+    }
+}
+
+impl FromStr for GYearMonth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = trim(s);
+        let parsed = parse_year(value).and_then(|(year, rest)| {
+            let month = parse_two_digits(rest.strip_prefix('-')?)?;
+            let offset = parse_offset(&rest[3..])?;
+            Self::new(year, month, offset).ok()
+        });
+
+        parsed.ok_or_else(|| invalid("gYearMonth", s))
+    }
+}
+
+impl fmt::Display for GYearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_year(self.year, f)?;
+        write!(f, "-{:02}", self.month)?;
+        write_offset(self.offset, f)
+    }
+}
+
+xsd_scalar!(GYearMonth);
+xsd_display!(GYearMonth);
+
+/// `xs:time`: a time of day with an optional timezone offset
+///
+/// The lexical form `24:00:00` is accepted as an alternative for `00:00:00`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanos: u32,
+    offset: Option<i16>,
+}
+
+impl Time {
+    /// Create a new time with an optional timezone offset in minutes
+    pub fn new(
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanos: u32,
+        offset: Option<i16>,
+    ) -> Result<Self, Error> {
+        check_offset(offset)?;
+        if hour > 23 || minute > 59 || second > 59 || nanos >= NANOS_PER_SECOND {
+            return Err(Error::UnexpectedValue(format!(
+                "invalid time {hour}:{minute}:{second}.{nanos}"
+            )));
+        }
+
+        Ok(Self {
+            hour,
+            minute,
+            second,
+            nanos,
+            offset,
+        })
+    }
+
+    /// The hour (0-23)
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// The minute (0-59)
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// The second (0-59)
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// The fractional second, in nanoseconds
+    pub fn nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// The timezone offset in minutes, if any
+    pub fn offset(&self) -> Option<i16> {
+        self.offset
+    }
+}
+
+impl FromStr for Time {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_time(trim(s)).ok_or_else(|| invalid("time", s))
+    }
+}
+
+fn parse_time(value: &str) -> Option<Time> {
+    // Valid times are ASCII, which keeps the fixed offsets below on character boundaries
+    let bytes = value.as_bytes();
+    if !value.is_ascii() || bytes.len() < 8 || bytes[2] != b':' || bytes[5] != b':' {
+        return None;
+    }
+
+    let hour = parse_two_digits(&value[..2])?;
+    let minute = parse_two_digits(&value[3..5])?;
+    let second = parse_two_digits(&value[6..8])?;
+    let mut rest = &value[8..];
+
+    let mut nanos = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let end = frac
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(frac.len());
+        nanos = parse_nanos(&frac[..end])?;
+        rest = &frac[end..];
+    }
+
+    let offset = parse_offset(rest)?;
+    match (hour, minute, second, nanos) {
+        (24, 0, 0, 0) => Time::new(0, 0, 0, 0, offset).ok(),
+        _ => Time::new(hour, minute, second, nanos, offset).ok(),
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        write_nanos(self.nanos, f)?;
+        write_offset(self.offset, f)
+    }
+}
+
+xsd_scalar!(Time);
+xsd_display!(Time);
+
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Validate an `xs:decimal` lexical value, returning it without surrounding whitespace
+#[cfg(feature = "rust_decimal")]
+pub(crate) fn decimal_lexical(value: &str) -> Option<&str> {
+    let value = trim(value);
+    split_decimal(value).map(|_| value)
+}
+
+/// Split an `xs:decimal` lexical value into its sign, integer and fractional digits
+fn split_decimal(value: &str) -> Option<(bool, &str, &str)> {
+    let (negative, unsigned) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+
+    let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if (int.is_empty() && frac.is_empty())
+        || !int.bytes().all(|b| b.is_ascii_digit())
+        || !frac.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    Some((negative, int, frac))
+}
+
+fn invalid(kind: &str, value: &str) -> Error {
+    Error::UnexpectedValue(format!("invalid xs:{kind} value `{value}`"))
+}
+
+fn is_xml_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
+
+fn trim(value: &str) -> &str {
+    value.trim_matches(is_xml_whitespace)
+}
+
+fn collapse(value: &str) -> String {
+    let mut collapsed = String::with_capacity(value.len());
+    for part in value
+        .split(is_xml_whitespace)
+        .filter(|part| !part.is_empty())
+    {
+        if !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(part);
+    }
+
+    collapsed
+}
+
+/// Whether `value` is an XML name without colons, as used for unprefixed element names
+pub(crate) fn is_ncname(value: &str) -> bool {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => {
+            first != ':' && is_name_start_char(first) && chars.all(|c| c != ':' && is_name_char(c))
+        }
+        None => false,
+    }
+}
+
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        ':' | 'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}'
+        )
+}
+
+fn parse_two_digits(value: &str) -> Option<u8> {
+    match value.as_bytes() {
+        [a @ b'0'..=b'9', b @ b'0'..=b'9', ..] => Some((a - b'0') * 10 + (b - b'0')),
+        _ => None,
+    }
+}
+
+/// Parse a fractional second into nanoseconds, rejecting precision beyond nanoseconds
+fn parse_nanos(frac: &str) -> Option<u32> {
+    if frac.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let (significant, rest) = frac.split_at(frac.len().min(9));
+    if rest.bytes().any(|b| b != b'0') {
+        return None;
+    }
+
+    let scale = 10u32.pow(9 - significant.len() as u32);
+    Some(significant.parse::<u32>().ok()? * scale)
+}
+
+fn write_nanos(nanos: u32, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if nanos == 0 {
+        return Ok(());
+    }
+
+    let digits = format!("{nanos:09}");
+    write!(f, ".{}", digits.trim_end_matches('0'))
+}
+
+/// Parse a year with at least four digits, returning the remaining input
+fn parse_year(value: &str) -> Option<(i32, &str)> {
+    let (negative, unsigned) = match value.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, value),
+    };
+
+    let end = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    let digits = &unsigned[..end];
+    if digits.len() < 4 || (digits.len() > 4 && digits.starts_with('0')) {
+        return None;
+    }
+
+    let year = digits.parse::<i32>().ok()?;
+    Some((if negative { -year } else { year }, &unsigned[end..]))
+}
+
+fn write_year(year: i32, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match year < 0 {
+        true => write!(f, "-{:04}", year.unsigned_abs()),
+        false => write!(f, "{year:04}"),
+    }
+}
+
+/// Parse an optional timezone offset (`Z` or `±hh:mm`) into minutes
+fn parse_offset(value: &str) -> Option<Option<i16>> {
+    let (sign, rest) = match value.as_bytes().first() {
+        None => return Some(None),
+        Some(b'Z') if value.len() == 1 => return Some(Some(0)),
+        Some(b'+') => (1, &value[1..]),
+        Some(b'-') => (-1, &value[1..]),
+        _ => return None,
+    };
+
+    if rest.len() != 5 || rest.as_bytes()[2] != b':' {
+        return None;
+    }
+
+    let hours = i16::from(parse_two_digits(&rest[..2])?);
+    let minutes = i16::from(parse_two_digits(&rest[3..])?);
+    if minutes > 59 || hours * 60 + minutes > 14 * 60 {
+        return None;
+    }
+
+    Some(Some(sign * (hours * 60 + minutes)))
+}
+
+/// Split an optional trailing timezone offset from a date or time value
+#[cfg(any(feature = "chrono", feature = "jiff", feature = "time"))]
+pub(crate) fn split_offset(value: &str) -> Option<(&str, Option<i16>)> {
+    if let Some(rest) = value.strip_suffix('Z') {
+        return Some((rest, Some(0)));
+    }
+
+    let bytes = value.as_bytes();
+    match bytes.len().checked_sub(6) {
+        Some(start) if matches!(bytes[start], b'+' | b'-') && bytes[start + 3] == b':' => {
+            Some((&value[..start], parse_offset(&value[start..])?))
+        }
+        _ => Some((value, None)),
+    }
+}
+
+fn write_offset(offset: Option<i16>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match offset {
+        None => Ok(()),
+        Some(0) => f.write_str("Z"),
+        Some(offset) => {
+            let sign = if offset < 0 { '-' } else { '+' };
+            let offset = offset.unsigned_abs();
+            write!(f, "{sign}{:02}:{:02}", offset / 60, offset % 60)
+        }
+    }
+}
+
+fn check_offset(offset: Option<i16>) -> Result<(), Error> {
+    match offset {
+        Some(offset) if !(-14 * 60..=14 * 60).contains(&offset) => Err(Error::UnexpectedValue(
+            format!("invalid timezone offset {offset}"),
+        )),
+        _ => Ok(()),
+    }
+}
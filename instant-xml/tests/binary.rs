@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Attachment<'a> {
+    #[xml(attribute, hex)]
+    digest: Vec<u8>,
+    #[xml(base64)]
+    data: Vec<u8>,
+    #[xml(hex)]
+    raw: Cow<'a, [u8]>,
+}
+
+#[test]
+fn binary() {
+    let v = Attachment {
+        digest: vec![0xde, 0xad, 0xbe, 0xef],
+        data: (0..=255).collect(),
+        raw: Cow::Owned(vec![0, 1]),
+    };
+    let encoded = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+P0BBQkNERUZHSElKS0xNTk9QUVJTVFVWV1hZWltcXV5fYGFiY2RlZmdoaWprbG1ub3BxcnN0dXZ3eHl6e3x9fn+AgYKDhIWGh4iJiouMjY6PkJGSk5SVlpeYmZqbnJ2en6ChoqOkpaanqKmqq6ytrq+wsbKztLW2t7i5uru8vb6/wMHCw8TFxsfIycrLzM3Oz9DR0tPU1dbX2Nna29zd3t/g4eLj5OXm5+jp6uvs7e7v8PHy8/T19vf4+fr7/P3+/w==";
+    let xml = format!(
+        r#"<Attachment digest="DEADBEEF"><data>{encoded}</data><raw>0001</raw></Attachment>"#
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(&xml).unwrap());
+
+    // Line-wrapped base64 and lower case hex are accepted
+    let wrapped = encoded
+        .as_bytes()
+        .chunks(76)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\r\n    ");
+    let xml = format!(
+        "<Attachment digest=\" deadbeef \"><data>\n    {wrapped}\n</data><raw>0001</raw></Attachment>"
+    );
+    assert_eq!(v, from_str(&xml).unwrap());
+}
+
+#[test]
+fn empty_binary() {
+    let v = Attachment {
+        digest: vec![],
+        data: vec![],
+        raw: Cow::Borrowed(&[]),
+    };
+    let xml = "<Attachment><data></data><raw></raw></Attachment>";
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+    assert_eq!(v, from_str("<Attachment />").unwrap());
+}
+
+#[test]
+fn binary_errors() {
+    assert_eq!(
+        from_str::<Attachment<'_>>("<Attachment><data>Zm9v!</data></Attachment>").unwrap_err(),
+        Error::UnexpectedValue("invalid base64 content for Attachment::data".to_owned())
+    );
+    assert_eq!(
+        from_str::<Attachment<'_>>(r#"<Attachment digest="abc" />"#).unwrap_err(),
+        Error::UnexpectedValue("invalid hex content for Attachment::digest".to_owned())
+    );
+    assert_eq!(
+        from_str::<Attachment<'_>>("<Attachment><raw>00</raw><raw>01</raw></Attachment>")
+            .unwrap_err(),
+        Error::DuplicateValue("Attachment::raw")
+    );
+
+    // An empty first element still counts as present
+    assert_eq!(
+        from_str::<Attachment<'_>>("<Attachment><data></data><data>AQ==</data></Attachment>")
+            .unwrap_err(),
+        Error::DuplicateValue("Attachment::data")
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Payload {
+    #[xml(direct, base64)]
+    content: Vec<u8>,
+}
+
+#[test]
+fn direct_binary() {
+    let v = Payload {
+        content: b"<xml>&".to_vec(),
+    };
+    let xml = "<Payload>PHhtbD4m</Payload>";
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
@@ -0,0 +1,133 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(choice, rename_all = "lowercase")]
+enum Status {
+    Active,
+    Inactive,
+    #[xml(rename = "on-hold")]
+    OnHold,
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Account {
+    status: Status,
+    previous: Option<Status>,
+}
+
+#[test]
+fn choice_enum() {
+    let v = Account {
+        status: Status::Active,
+        previous: None,
+    };
+    let xml = r#"<Account><status><active /></status></Account>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let v = Account {
+        status: Status::Inactive,
+        previous: Some(Status::OnHold),
+    };
+    let xml = r#"<Account><status><inactive /></status><previous><on-hold /></previous></Account>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = "<Account>\n  <status>\n    <active></active>\n  </status>\n</Account>";
+    assert_eq!(
+        from_str::<Account>(xml).unwrap(),
+        Account {
+            status: Status::Active,
+            previous: None,
+        }
+    );
+}
+
+#[test]
+fn choice_enum_errors() {
+    assert_eq!(
+        from_str::<Account>("<Account><status></status></Account>").unwrap_err(),
+        Error::MissingValue("Account::status")
+    );
+
+    assert!(matches!(
+        from_str::<Account>("<Account><status><unknown /></status></Account>").unwrap_err(),
+        Error::UnexpectedTag(_)
+    ));
+
+    assert!(matches!(
+        from_str::<Account>("<Account><status>active</status></Account>").unwrap_err(),
+        Error::UnexpectedNode(_)
+    ));
+
+    assert_eq!(
+        from_str::<Account>(
+            "<Account><status><active /></status><status><inactive /></status></Account>"
+        )
+        .unwrap_err(),
+        Error::DuplicateValue("Account::status")
+    );
+
+    // Nothing but whitespace may follow the variant element
+    assert!(matches!(
+        from_str::<Account>("<Account><status><active/><inactive/></status></Account>")
+            .unwrap_err(),
+        Error::UnexpectedNode(_)
+    ));
+    assert!(matches!(
+        from_str::<Account>("<Account><status><active/>junk</status></Account>").unwrap_err(),
+        Error::UnexpectedNode(_)
+    ));
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(ns("URI"))]
+struct NsAccount {
+    status: Status,
+}
+
+#[test]
+fn choice_enum_inherits_ns() {
+    let v = NsAccount {
+        status: Status::Active,
+    };
+    let xml = r#"<NsAccount xmlns="URI"><status><active /></status></NsAccount>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = r#"<NsAccount xmlns="URI"><status><active xmlns="OTHER" /></status></NsAccount>"#;
+    assert!(matches!(
+        from_str::<NsAccount>(xml).unwrap_err(),
+        Error::UnexpectedTag(_)
+    ));
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(choice, ns("CHOICE", c = "CHOICE"))]
+enum Shape {
+    Circle,
+    Square,
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(ns("URI", c = "CHOICE"))]
+struct Drawing {
+    shape: Vec<Shape>,
+}
+
+#[test]
+fn choice_enum_ns() {
+    let v = Drawing {
+        shape: vec![Shape::Circle, Shape::Square],
+    };
+    let xml = concat!(
+        r#"<Drawing xmlns="URI" xmlns:c="CHOICE">"#,
+        "<shape><c:Circle /></shape>",
+        "<shape><c:Square /></shape>",
+        "</Drawing>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
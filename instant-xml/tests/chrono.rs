@@ -1,9 +1,9 @@
 #![cfg(feature = "chrono")]
 
-use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use similar_asserts::assert_eq;
 
-use instant_xml::{from_str, to_string, FromXml, ToXml};
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
 
 #[derive(Debug, Eq, PartialEq, FromXml, ToXml)]
 struct Test<T> {
@@ -30,3 +30,131 @@ fn naive_datetime() {
     assert_eq!(to_string(&test).unwrap(), xml);
     assert_eq!(from_str::<Test<NaiveDateTime>>(xml).unwrap(), test);
 }
+
+#[test]
+fn fixed_offset() {
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let dt = offset.with_ymd_and_hms(2022, 11, 21, 21, 17, 23).unwrap();
+    let test = Test { dt };
+    let xml = "<Test><dt>2022-11-21T21:17:23+02:00</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+
+    let parsed = from_str::<Test<DateTime<FixedOffset>>>(xml).unwrap();
+    assert_eq!(parsed, test);
+    assert_eq!(parsed.dt.offset(), &offset);
+
+    let local = from_str::<Test<DateTime<Local>>>(xml).unwrap();
+    assert_eq!(local.dt, dt);
+    assert_eq!(
+        from_str::<Test<DateTime<Local>>>(&to_string(&local).unwrap()).unwrap(),
+        local
+    );
+}
+
+#[test]
+fn naive_date_and_time() {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let test = Test { dt: date };
+    assert_eq!(
+        to_string(&test).unwrap(),
+        "<Test><dt>2024-01-01</dt></Test>"
+    );
+    // Timezone suffixes are accepted and discarded
+    for input in [
+        "2024-01-01",
+        "2024-01-01Z",
+        "2024-01-01+05:30",
+        "2024-01-01-14:00",
+    ] {
+        let xml = format!("<Test><dt>{input}</dt></Test>");
+        assert_eq!(from_str::<Test<NaiveDate>>(&xml).unwrap(), test, "{input}");
+    }
+
+    let time = NaiveTime::from_hms_milli_opt(13, 20, 0, 500).unwrap();
+    let test = Test { dt: time };
+    assert_eq!(
+        to_string(&test).unwrap(),
+        "<Test><dt>13:20:00.500</dt></Test>"
+    );
+    for input in ["13:20:00.5", "13:20:00.500Z", "13:20:00.5-05:00"] {
+        let xml = format!("<Test><dt>{input}</dt></Test>");
+        assert_eq!(from_str::<Test<NaiveTime>>(&xml).unwrap(), test, "{input}");
+    }
+
+    assert!(from_str::<Test<NaiveDate>>("<Test><dt>2024-01-01+15:00</dt></Test>").is_err());
+    assert!(from_str::<Test<NaiveTime>>("<Test><dt>13:20</dt></Test>").is_err());
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Formats {
+    #[xml(attribute, format = "%d/%m/%Y")]
+    date: NaiveDate,
+    #[xml(format = "%Y%m%d%H%M%S")]
+    stamp: DateTime<Utc>,
+    #[xml(format = "%d %b %Y %H:%M %z")]
+    local: Option<DateTime<FixedOffset>>,
+    #[xml(direct, format = "%H.%M")]
+    time: NaiveTime,
+}
+
+#[test]
+fn formats() {
+    let v = Formats {
+        date: NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+        stamp: Utc.with_ymd_and_hms(2024, 3, 1, 12, 30, 45).unwrap(),
+        local: Some(
+            FixedOffset::west_opt(3600)
+                .unwrap()
+                .with_ymd_and_hms(2024, 3, 2, 8, 0, 0)
+                .unwrap(),
+        ),
+        time: NaiveTime::from_hms_opt(9, 15, 0).unwrap(),
+    };
+    let xml = concat!(
+        r#"<Formats date="29/02/2024"><stamp>20240301123045</stamp>"#,
+        "<local>02 Mar 2024 08:00 -0100</local>09.15</Formats>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Formats>(xml).unwrap(), v);
+
+    let v = Formats { local: None, ..v };
+    let xml = r#"<Formats date="29/02/2024"><stamp>20240301123045</stamp>09.15</Formats>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Formats>(xml).unwrap(), v);
+
+    assert_eq!(
+        from_str::<Formats>(&xml.replace("29/02/2024", "2024-02-29")).unwrap_err(),
+        Error::UnexpectedValue(
+            "unable to parse `2024-02-29` with format `%d/%m/%Y` for Formats::date".to_owned()
+        )
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Labeled {
+    #[xml(attribute, format = "%H:%M \"%p\" & co")]
+    time: NaiveTime,
+    #[xml(format = "<%Y-%m-%d>")]
+    date: NaiveDate,
+    #[xml(format = "%H:%M %z")]
+    zoned: Option<NaiveTime>,
+}
+
+#[test]
+fn formats_escaped() {
+    let v = Labeled {
+        time: NaiveTime::from_hms_opt(9, 15, 0).unwrap(),
+        date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        zoned: None,
+    };
+    let xml = r#"<Labeled time="09:15 &quot;AM&quot; &amp; co"><date>&lt;2024-01-01&gt;</date></Labeled>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Labeled>(xml).unwrap(), v);
+
+    // Offsets are accepted and discarded by naive types
+    let xml = xml.replace("</Labeled>", "<zoned>10:00 +0100</zoned></Labeled>");
+    assert_eq!(
+        from_str::<Labeled>(&xml).unwrap().zoned,
+        NaiveTime::from_hms_opt(10, 0, 0)
+    );
+}
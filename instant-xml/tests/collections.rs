@@ -0,0 +1,114 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Item {
+    #[xml(attribute)]
+    id: u32,
+}
+
+#[derive(Debug, FromXml, PartialEq, ToXml)]
+struct Collections {
+    queue: VecDeque<Item>,
+    tag: BTreeSet<String>,
+    flag: HashSet<u8>,
+    point: [i32; 2],
+}
+
+#[test]
+fn collections() {
+    let v = Collections {
+        queue: VecDeque::from([Item { id: 1 }, Item { id: 2 }]),
+        tag: BTreeSet::from(["a".to_owned(), "b".to_owned()]),
+        flag: HashSet::from([7]),
+        point: [3, 4],
+    };
+    let xml = concat!(
+        "<Collections>",
+        r#"<Item id="1" /><Item id="2" />"#,
+        "<tag>a</tag><tag>b</tag>",
+        "<flag>7</flag>",
+        "<point>3</point><point>4</point>",
+        "</Collections>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
+
+#[test]
+fn collection_errors() {
+    assert_eq!(
+        from_str::<Collections>("<Collections><tag>a</tag><tag>a</tag></Collections>").unwrap_err(),
+        Error::DuplicateValue("Collections::tag")
+    );
+    assert_eq!(
+        from_str::<Collections>("<Collections><flag>1</flag><flag>1</flag></Collections>")
+            .unwrap_err(),
+        Error::DuplicateValue("Collections::flag")
+    );
+    assert_eq!(
+        from_str::<Collections>("<Collections><point>1</point></Collections>").unwrap_err(),
+        Error::TooFewOccurrences {
+            field: "Collections::point",
+            count: 1,
+            min: 2
+        }
+    );
+    assert_eq!(
+        from_str::<Collections>(
+            "<Collections><point>1</point><point>2</point><point>3</point></Collections>"
+        )
+        .unwrap_err(),
+        Error::TooManyOccurrences {
+            field: "Collections::point",
+            count: 3,
+            max: 2
+        }
+    );
+}
+
+#[derive(Debug, FromXml, PartialEq, ToXml)]
+struct Pointers {
+    boxed: Box<str>,
+    shared: Rc<str>,
+    sync: Arc<str>,
+    item: Rc<Item>,
+    count: Arc<u32>,
+    #[xml(attribute)]
+    hits: Cell<u32>,
+    label: RefCell<String>,
+}
+
+#[test]
+fn pointers() {
+    let v = Pointers {
+        boxed: "a & b".into(),
+        shared: "".into(),
+        sync: "c".into(),
+        item: Rc::new(Item { id: 5 }),
+        count: Arc::new(9),
+        hits: Cell::new(2),
+        label: RefCell::new("x".to_owned()),
+    };
+    let xml = concat!(
+        r#"<Pointers hits="2">"#,
+        "<boxed>a &amp; b</boxed><shared></shared><sync>c</sync>",
+        r#"<Item id="5" /><count>9</count>"#,
+        "<label>x</label>",
+        "</Pointers>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let _borrow = v.label.borrow_mut();
+    assert_eq!(
+        to_string(&v).unwrap_err(),
+        Error::UnexpectedState("value is mutably borrowed")
+    );
+}
@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(rename = "point")]
+struct PointWire {
+    #[xml(attribute)]
+    x: i32,
+    #[xml(attribute)]
+    y: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, FromXml, ToXml)]
+#[xml(from = "PointWire", into = "PointWire")]
+struct Point(i32, i32);
+
+impl From<PointWire> for Point {
+    fn from(wire: PointWire) -> Self {
+        Self(wire.x, wire.y)
+    }
+}
+
+impl From<Point> for PointWire {
+    fn from(point: Point) -> Self {
+        Self {
+            x: point.0,
+            y: point.1,
+        }
+    }
+}
+
+/// Converts to `None` when blank, so the element is omitted
+#[derive(Clone, Debug, PartialEq, ToXml)]
+#[xml(into = "Option<String>")]
+struct Remark(String);
+
+impl From<Remark> for Option<String> {
+    fn from(remark: Remark) -> Self {
+        match remark.0.trim().is_empty() {
+            true => None,
+            false => Some(remark.0),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, ToXml)]
+struct Ticket {
+    id: u32,
+    remark: Remark,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(try_from = "u8")]
+struct Percentage(u8);
+
+impl TryFrom<u8> for Percentage {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0..=100 => Ok(Self(value)),
+            _ => Err(format!("{value} is not a percentage")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(try_from = "Cow<'a, str>")]
+struct Name<'a>(Cow<'a, str>);
+
+impl<'a> TryFrom<Cow<'a, str>> for Name<'a> {
+    type Error = &'static str;
+
+    fn try_from(value: Cow<'a, str>) -> Result<Self, Self::Error> {
+        match value.is_empty() {
+            true => Err("empty name"),
+            false => Ok(Self(value)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+struct Shape<'a> {
+    #[xml(attribute, borrow)]
+    name: Name<'a>,
+    opacity: Percentage,
+    points: Vec<Point>,
+}
+
+#[test]
+fn from_into() {
+    let point = Point(1, -2);
+    let xml = r#"<point x="1" y="-2" />"#;
+    assert_eq!(to_string(&point).unwrap(), xml);
+    assert_eq!(from_str::<Point>(xml).unwrap(), point);
+}
+
+#[test]
+fn into_absent() {
+    let ticket = Ticket {
+        id: 1,
+        remark: Remark("urgent".into()),
+    };
+    assert_eq!(
+        to_string(&ticket).unwrap(),
+        "<Ticket><id>1</id><remark>urgent</remark></Ticket>"
+    );
+
+    let ticket = Ticket {
+        id: 2,
+        remark: Remark(" ".into()),
+    };
+    assert_eq!(to_string(&ticket).unwrap(), "<Ticket><id>2</id></Ticket>");
+}
+
+#[test]
+fn try_from() {
+    let xml = r#"<Shape name="triangle"><opacity>50</opacity><point x="0" y="0" /><point x="1" y="0" /><point x="0" y="1" /></Shape>"#;
+    let shape = from_str::<Shape<'_>>(xml).unwrap();
+    assert_eq!(
+        shape,
+        Shape {
+            name: Name("triangle".into()),
+            opacity: Percentage(50),
+            points: vec![Point(0, 0), Point(1, 0), Point(0, 1)],
+        }
+    );
+
+    let xml = r#"<Shape name="triangle"><opacity>150</opacity></Shape>"#;
+    assert_eq!(
+        from_str::<Shape<'_>>(xml).unwrap_err(),
+        Error::UnexpectedValue("150 is not a percentage for Shape::opacity".to_owned())
+    );
+
+    let xml = r#"<Shape name=""><opacity>50</opacity></Shape>"#;
+    assert_eq!(
+        from_str::<Shape<'_>>(xml).unwrap_err(),
+        Error::UnexpectedValue("empty name for Shape::name".to_owned())
+    );
+
+    let xml = r#"<Shape name="triangle"></Shape>"#;
+    assert_eq!(
+        from_str::<Shape<'_>>(xml).unwrap_err(),
+        Error::MissingValue("Shape::opacity")
+    );
+}
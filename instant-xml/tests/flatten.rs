@@ -0,0 +1,157 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Audit {
+    #[xml(attribute)]
+    revision: u32,
+    #[xml(attribute)]
+    author: Option<String>,
+    created: String,
+    updated: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Document {
+    #[xml(attribute)]
+    id: u32,
+    title: String,
+    #[xml(flatten)]
+    audit: Audit,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Folder {
+    #[xml(attribute)]
+    name: String,
+    #[xml(flatten)]
+    audit: Audit,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Ownership {
+    owner: String,
+    #[xml(flatten)]
+    audit: Audit,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Archive {
+    #[xml(flatten)]
+    ownership: Ownership,
+    #[xml(attribute)]
+    compressed: bool,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Empty {}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Revision {
+    #[xml(flatten)]
+    audit: Audit,
+    #[xml(flatten)]
+    empty: Empty,
+}
+
+#[test]
+fn flatten() {
+    let v = Document {
+        id: 7,
+        title: "Notes".to_owned(),
+        audit: Audit {
+            revision: 3,
+            author: Some("ann".to_owned()),
+            created: "2024-01-01".to_owned(),
+            updated: None,
+        },
+        tags: vec!["a".to_owned(), "b".to_owned()],
+    };
+
+    let xml = concat!(
+        r#"<Document id="7" revision="3" author="ann">"#,
+        "<title>Notes</title><created>2024-01-01</created><tags>a</tags><tags>b</tags>",
+        "</Document>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Document>(xml).unwrap(), v);
+
+    // Flattened fields can appear in any order relative to the parent's fields
+    let xml = concat!(
+        r#"<Document revision="3" id="7" author="ann">"#,
+        "<created>2024-01-01</created><tags>a</tags><title>Notes</title><tags>b</tags>",
+        "<unknown />",
+        "</Document>"
+    );
+    assert_eq!(from_str::<Document>(xml).unwrap(), v);
+
+    let xml = r#"<Folder name="docs"><created>2024-01-01</created></Folder>"#;
+    assert_eq!(
+        from_str::<Folder>(xml).unwrap_err(),
+        Error::MissingValue("Audit::revision")
+    );
+
+    let v = Folder {
+        name: "docs".to_owned(),
+        audit: Audit {
+            revision: 1,
+            author: None,
+            created: "2024-01-01".to_owned(),
+            updated: Some("2024-02-01".to_owned()),
+        },
+    };
+    let xml = concat!(
+        r#"<Folder name="docs" revision="1">"#,
+        "<created>2024-01-01</created><updated>2024-02-01</updated>",
+        "</Folder>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Folder>(xml).unwrap(), v);
+}
+
+#[test]
+fn nested_flatten() {
+    let v = Archive {
+        ownership: Ownership {
+            owner: "ops".to_owned(),
+            audit: Audit {
+                revision: 2,
+                author: None,
+                created: "2024-01-01".to_owned(),
+                updated: None,
+            },
+        },
+        compressed: true,
+    };
+
+    let xml = concat!(
+        r#"<Archive revision="2" compressed="true">"#,
+        "<owner>ops</owner><created>2024-01-01</created>",
+        "</Archive>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Archive>(xml).unwrap(), v);
+}
+
+#[test]
+fn without_own_fields() {
+    let xml = "<Empty />";
+    assert_eq!(to_string(&Empty {}).unwrap(), xml);
+    assert_eq!(from_str::<Empty>(xml).unwrap(), Empty {});
+
+    let v = Revision {
+        audit: Audit {
+            revision: 3,
+            author: None,
+            created: "2024-02-01".to_owned(),
+            updated: None,
+        },
+        empty: Empty {},
+    };
+
+    let xml = r#"<Revision revision="3"><created>2024-02-01</created></Revision>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Revision>(xml).unwrap(), v);
+}
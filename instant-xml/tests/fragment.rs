@@ -0,0 +1,76 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::de::Limits;
+use instant_xml::{from_str_fragment, Error, Fragments, FromXml};
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(rename = "event")]
+struct Event {
+    #[xml(attribute)]
+    id: u32,
+    message: Option<String>,
+}
+
+#[test]
+fn events() {
+    let xml = r#"<event id="1" />
+        <!-- second -->
+        <event id="2"><message>hello</message></event><event id="3" />
+    "#;
+
+    let events = from_str_fragment::<Event>(xml)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            Event {
+                id: 1,
+                message: None,
+            },
+            Event {
+                id: 2,
+                message: Some("hello".to_owned()),
+            },
+            Event {
+                id: 3,
+                message: None
+            },
+        ]
+    );
+
+    assert_eq!(from_str_fragment::<Event>("").count(), 0);
+}
+
+#[test]
+fn errors() {
+    // Iteration stops after the first error
+    let mut events = from_str_fragment::<Event>(r#"<event id="1" />text<event id="2" />"#);
+    assert!(events.next().unwrap().is_ok());
+    assert!(matches!(
+        events.next().unwrap().unwrap_err(),
+        Error::UnexpectedNode(_)
+    ));
+    assert!(events.next().is_none());
+
+    let mut events = from_str_fragment::<Event>(r#"<event id="1" /><other />"#);
+    assert!(events.next().unwrap().is_ok());
+    assert!(matches!(
+        events.next().unwrap().unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+}
+
+#[test]
+fn limits() {
+    let mut limits = Limits::default();
+    limits.max_levels = 1;
+
+    let xml = r#"<event id="1" /><event id="2"><message>hello</message></event>"#;
+    let mut events = Fragments::<Event>::new(xml).with_limits(limits);
+    assert!(events.next().unwrap().is_ok());
+    assert!(matches!(
+        events.next().unwrap().unwrap_err(),
+        Error::Other(_)
+    ));
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "jiff")]
+
+use jiff::civil::{self, date, time};
+use jiff::{tz, Timestamp, Zoned};
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, PartialEq, FromXml, ToXml)]
+struct Test<T> {
+    dt: T,
+}
+
+#[test]
+fn timestamp() {
+    let test = Test {
+        dt: Timestamp::from_second(1_669_065_443).unwrap(),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23Z</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+    assert_eq!(from_str::<Test<Timestamp>>(xml).unwrap(), test);
+
+    let offset = "<Test><dt>2022-11-21T23:17:23+02:00</dt></Test>";
+    assert_eq!(from_str::<Test<Timestamp>>(offset).unwrap(), test);
+}
+
+#[test]
+fn zoned() {
+    let offset = tz::offset(2);
+    let test = Test {
+        dt: date(2022, 11, 21)
+            .at(21, 17, 23, 0)
+            .to_zoned(tz::TimeZone::fixed(offset))
+            .unwrap(),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23+02:00</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+
+    let parsed = from_str::<Test<Zoned>>(xml).unwrap();
+    assert_eq!(parsed, test);
+    assert_eq!(parsed.dt.offset(), offset);
+
+    let annotated = "<Test><dt>2022-11-21T21:17:23+02:00[+02:00]</dt></Test>";
+    assert_eq!(from_str::<Test<Zoned>>(annotated).unwrap(), test);
+    assert!(from_str::<Test<Zoned>>("<Test><dt>2022-11-21T21:17:23</dt></Test>").is_err());
+}
+
+#[test]
+fn civil() {
+    let test = Test {
+        dt: date(2022, 11, 21).at(21, 17, 23, 500_000_000),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23.5</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+    assert_eq!(from_str::<Test<civil::DateTime>>(xml).unwrap(), test);
+
+    let test = Test {
+        dt: date(2024, 1, 1),
+    };
+    assert_eq!(
+        to_string(&test).unwrap(),
+        "<Test><dt>2024-01-01</dt></Test>"
+    );
+    for input in ["2024-01-01", "2024-01-01Z", "2024-01-01+05:30"] {
+        let xml = format!("<Test><dt>{input}</dt></Test>");
+        assert_eq!(
+            from_str::<Test<civil::Date>>(&xml).unwrap(),
+            test,
+            "{input}"
+        );
+    }
+
+    let test = Test {
+        dt: time(13, 20, 0, 500_000_000),
+    };
+    assert_eq!(
+        to_string(&test).unwrap(),
+        "<Test><dt>13:20:00.5</dt></Test>"
+    );
+    for input in ["13:20:00.5", "13:20:00.500Z", "13:20:00.5-05:00"] {
+        let xml = format!("<Test><dt>{input}</dt></Test>");
+        assert_eq!(
+            from_str::<Test<civil::Time>>(&xml).unwrap(),
+            test,
+            "{input}"
+        );
+    }
+
+    assert!(from_str::<Test<civil::Date>>("<Test><dt>2024-01-01+15:00</dt></Test>").is_err());
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Formats {
+    #[xml(attribute, format = "%d/%m/%Y")]
+    date: civil::Date,
+    #[xml(format = "%Y%m%d%H%M%S%z")]
+    stamp: Timestamp,
+    #[xml(direct, format = "%H.%M")]
+    time: Option<civil::Time>,
+}
+
+#[test]
+fn formats() {
+    let v = Formats {
+        date: date(2024, 2, 29),
+        stamp: "2024-03-01T12:30:45Z".parse().unwrap(),
+        time: Some(time(9, 15, 0, 0)),
+    };
+    let xml = r#"<Formats date="29/02/2024"><stamp>20240301123045+0000</stamp>09.15</Formats>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Formats>(xml).unwrap(), v);
+
+    assert_eq!(
+        from_str::<Formats>(&xml.replace("29/02/2024", "2024-02-29")).unwrap_err(),
+        Error::UnexpectedValue(
+            "unable to parse `2024-02-29` with format `%d/%m/%Y` for Formats::date".to_owned()
+        )
+    );
+}
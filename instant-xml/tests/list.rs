@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, List, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Shape {
+    #[xml(attribute, list)]
+    class: Vec<String>,
+    #[xml(list)]
+    coords: Vec<i32>,
+    weights: List<f64>,
+}
+
+#[test]
+fn list() {
+    let v = Shape {
+        class: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+        coords: vec![1, 2, 3],
+        weights: List(vec![0.5, 1.0]),
+    };
+    let xml =
+        r#"<Shape class="a b c"><coords>1 2 3</coords><weights>5.0E-1 1.0E0</weights></Shape>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = "<Shape class=\"  a\tb\n c \"><coords>\n  1\n  2\n  3\n</coords><weights>0.5   1</weights></Shape>";
+    assert_eq!(v, from_str(xml).unwrap());
+}
+
+#[test]
+fn empty_list() {
+    let v = Shape {
+        class: vec![],
+        coords: vec![],
+        weights: List(vec![]),
+    };
+    let xml = r#"<Shape><coords></coords><weights></weights></Shape>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+    assert_eq!(
+        v,
+        from_str(r#"<Shape class=" "><weights /></Shape>"#).unwrap()
+    );
+}
+
+#[test]
+fn list_errors() {
+    assert!(matches!(
+        from_str::<Shape>("<Shape><coords>1 two 3</coords><weights /></Shape>").unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+
+    assert_eq!(
+        from_str::<Shape>("<Shape><coords>1</coords><coords>2</coords><weights /></Shape>")
+            .unwrap_err(),
+        Error::DuplicateValue("Shape::coords")
+    );
+
+    // An empty first element still counts as present
+    assert_eq!(
+        from_str::<Shape>("<Shape><coords></coords><coords>2</coords><weights /></Shape>")
+            .unwrap_err(),
+        Error::DuplicateValue("Shape::coords")
+    );
+
+    assert_eq!(
+        from_str::<Shape>("<Shape><coords>1</coords></Shape>").unwrap_err(),
+        Error::MissingValue("Shape::weights")
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Tokens<'a> {
+    #[xml(attribute)]
+    kind: List<Cow<'a, str>>,
+    #[xml(direct, list)]
+    values: Vec<u8>,
+}
+
+#[test]
+fn direct_list() {
+    let v = Tokens {
+        kind: List(vec!["x".into(), "&y".into()]),
+        values: vec![4, 5, 6],
+    };
+    let xml = r#"<Tokens kind="x &amp;y">4 5 6</Tokens>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
@@ -0,0 +1,139 @@
+use std::collections::{BTreeMap, HashMap};
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Item {
+    #[xml(attribute)]
+    id: u32,
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Config {
+    settings: BTreeMap<String, String>,
+    #[xml(rename = "limit", key_attribute = "name")]
+    limits: BTreeMap<String, u32>,
+    #[xml(
+        wrapper = "items",
+        rename = "entry",
+        key_element = "key",
+        value_element = "value"
+    )]
+    items: BTreeMap<u32, Item>,
+}
+
+#[test]
+fn map() {
+    let v = Config {
+        settings: BTreeMap::from([
+            ("color".to_owned(), "red".to_owned()),
+            ("size".to_owned(), "".to_owned()),
+        ]),
+        limits: BTreeMap::from([("depth".to_owned(), 3), ("width".to_owned(), 10)]),
+        items: BTreeMap::from([(1, Item { id: 7 })]),
+    };
+    let xml = concat!(
+        "<Config>",
+        "<settings><color>red</color><size></size></settings>",
+        r#"<limit name="depth">3</limit><limit name="width">10</limit>"#,
+        r#"<items><entry><key>1</key><Item id="7" /></entry></items>"#,
+        "</Config>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = concat!(
+        "<Config>",
+        "<settings>\n  <color>red</color>\n  <size />\n</settings>",
+        r#"<limit name="depth">3</limit><items></items><limit name="width">10</limit>"#,
+        r#"<items><entry><Item id="7" /><key>1</key></entry></items>"#,
+        "</Config>"
+    );
+    assert_eq!(
+        from_str::<Config>(xml).unwrap_err(),
+        Error::DuplicateValue("Config::items")
+    );
+    assert_eq!(v, from_str(&xml.replace("<items></items>", "")).unwrap());
+}
+
+#[test]
+fn empty_map() {
+    let v = Config {
+        settings: BTreeMap::new(),
+        limits: BTreeMap::new(),
+        items: BTreeMap::new(),
+    };
+    let xml = "<Config><settings></settings><items></items></Config>";
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+    assert_eq!(v, from_str("<Config />").unwrap());
+}
+
+#[test]
+fn duplicate_keys() {
+    assert_eq!(
+        from_str::<Config>("<Config><settings><a>1</a><a>2</a></settings></Config>").unwrap_err(),
+        Error::DuplicateValue("Config::settings")
+    );
+    assert_eq!(
+        from_str::<Config>(
+            r#"<Config><limit name="a">1</limit><limit name="a">2</limit></Config>"#
+        )
+        .unwrap_err(),
+        Error::DuplicateValue("Config::limits")
+    );
+    assert_eq!(
+        from_str::<Config>(concat!(
+            "<Config><items>",
+            r#"<entry><key>1</key><Item id="1" /></entry>"#,
+            r#"<entry><key>1</key><Item id="2" /></entry>"#,
+            "</items></Config>"
+        ))
+        .unwrap_err(),
+        Error::DuplicateValue("Config::items")
+    );
+    assert_eq!(
+        from_str::<Config>(r#"<Config><limit>1</limit></Config>"#).unwrap_err(),
+        Error::MissingValue("Config::limits")
+    );
+}
+
+#[test]
+fn invalid_keys() {
+    // Keys used as element names must be valid names
+    for key in ["1", "a b", "x&y", "p:q", ""] {
+        let v = Config {
+            settings: BTreeMap::from([(key.to_owned(), "v".to_owned())]),
+            limits: BTreeMap::new(),
+            items: BTreeMap::new(),
+        };
+        assert!(matches!(
+            to_string(&v).unwrap_err(),
+            Error::UnexpectedValue(_)
+        ));
+    }
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(ns("URI", bar = "BAR"))]
+struct Labels {
+    #[xml(ns("BAR"))]
+    labels: HashMap<String, u8>,
+}
+
+#[test]
+fn map_ns() {
+    let v = Labels {
+        labels: HashMap::from([("x".to_owned(), 1)]),
+    };
+    let xml =
+        r#"<Labels xmlns="URI" xmlns:bar="BAR"><bar:labels><bar:x>1</bar:x></bar:labels></Labels>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    // Children from other namespaces are ignored
+    let xml = r#"<Labels xmlns="URI" xmlns:bar="BAR"><bar:labels><bar:x>1</bar:x><y>2</y></bar:labels></Labels>"#;
+    assert_eq!(v, from_str(xml).unwrap());
+}
@@ -0,0 +1,106 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Host {
+    #[xml(attribute)]
+    v4: Ipv4Addr,
+    #[xml(attribute)]
+    v6: Ipv6Addr,
+    any: IpAddr,
+    listen: SocketAddr,
+    listen4: SocketAddrV4,
+    listen6: SocketAddrV6,
+}
+
+#[test]
+fn addresses() {
+    let v = Host {
+        v4: Ipv4Addr::new(192, 0, 2, 1),
+        v6: Ipv6Addr::LOCALHOST,
+        any: IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        listen: "[2001:db8::1]:443".parse().unwrap(),
+        listen4: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 80),
+        listen6: SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0),
+    };
+    let xml = concat!(
+        r#"<Host v4="192.0.2.1" v6="::1"><any>::</any><listen>[2001:db8::1]:443</listen>"#,
+        "<listen4>127.0.0.1:80</listen4><listen6>[::1]:8080</listen6></Host>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Host>(xml).unwrap(), v);
+
+    // Surrounding whitespace is ignored for all address types
+    let xml = concat!(
+        r#"<Host v4=" 192.0.2.1 " v6="::1"><any> :: </any><listen> [2001:db8::1]:443 </listen>"#,
+        "<listen4>\n127.0.0.1:80\n</listen4><listen6>[::1]:8080</listen6></Host>"
+    );
+    assert_eq!(from_str::<Host>(xml).unwrap(), v);
+}
+
+#[test]
+fn wrong_family() {
+    let xml = concat!(
+        r#"<Host v4="192.0.2.1" v6="::1"><any>::</any><listen>[2001:db8::1]:443</listen>"#,
+        "<listen4>127.0.0.1:80</listen4><listen6>[::1]:8080</listen6></Host>"
+    );
+
+    assert_eq!(
+        from_str::<Host>(&xml.replace("v4=\"192.0.2.1\"", "v4=\"::2\"")).unwrap_err(),
+        Error::UnexpectedValue(
+            "expected IPv4 address but found IPv6 address `::2` for Host::v4".into()
+        )
+    );
+    assert_eq!(
+        from_str::<Host>(&xml.replace("v6=\"::1\"", "v6=\"10.0.0.1\"")).unwrap_err(),
+        Error::UnexpectedValue(
+            "expected IPv6 address but found IPv4 address `10.0.0.1` for Host::v6".into()
+        )
+    );
+    assert_eq!(
+        from_str::<Host>(&xml.replace("[::1]:8080", "127.0.0.1:8080")).unwrap_err(),
+        Error::UnexpectedValue(
+            "expected IPv6 socket address but found IPv4 socket address `127.0.0.1:8080` for Host::listen6"
+                .into()
+        )
+    );
+    assert_eq!(
+        from_str::<Host>(&xml.replace("v4=\"192.0.2.1\"", "v4=\"999.0.0.1\"")).unwrap_err(),
+        Error::UnexpectedValue("unable to parse IPv4 address from `999.0.0.1` for Host::v4".into())
+    );
+}
+
+#[cfg(feature = "ipnet")]
+#[test]
+fn networks() {
+    use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+
+    #[derive(Debug, PartialEq, FromXml, ToXml)]
+    struct Route {
+        #[xml(attribute)]
+        v4: Ipv4Net,
+        #[xml(attribute)]
+        v6: Ipv6Net,
+        #[xml(direct)]
+        any: IpNet,
+    }
+
+    let v = Route {
+        v4: "10.0.0.0/8".parse().unwrap(),
+        v6: "2001:db8::/32".parse().unwrap(),
+        any: "192.168.1.0/24".parse().unwrap(),
+    };
+    let xml = r#"<Route v4="10.0.0.0/8" v6="2001:db8::/32">192.168.1.0/24</Route>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Route>(xml).unwrap(), v);
+
+    assert_eq!(
+        from_str::<Route>(&xml.replace("10.0.0.0/8", "fd00::/8")).unwrap_err(),
+        Error::UnexpectedValue(
+            "expected IPv4 network but found IPv6 network `fd00::/8` for Route::v4".into()
+        )
+    );
+}
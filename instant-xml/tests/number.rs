@@ -1,4 +1,6 @@
-use instant_xml::{from_str, FromXml};
+use std::num::{NonZeroI64, NonZeroU32};
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
 use similar_asserts::assert_eq;
 
 #[derive(FromXml, PartialEq, Debug)]
@@ -36,3 +38,106 @@ fn deserialize_spaced_numbers_fields() {
     let xml = r#"<Number><i_8>  -1 </i_8><i_16>-32456 </i_16><i_32>-6034568 </i_32><i_64>-1245789630056 </i_64><i_size>-125698389 </i_size><u_8>9 </u_8><u_16>64469   </u_16><u_32>6034568 </u_32><u_64> 99245789630056 </u_64><u_size>125698389 </u_size><f_32>    -12.5683   </f_32><f_64>  104568.568932 </f_64></Number>"#;
     assert_eq!(v, from_str(xml).unwrap());
 }
+
+#[derive(FromXml, ToXml, PartialEq, Debug)]
+struct Wide {
+    i_128: i128,
+    u_128: u128,
+    nz_u32: NonZeroU32,
+    nz_i64: NonZeroI64,
+}
+
+#[test]
+fn wide_and_non_zero_numbers() {
+    let v = Wide {
+        i_128: i128::MIN,
+        u_128: u128::MAX,
+        nz_u32: NonZeroU32::new(7).unwrap(),
+        nz_i64: NonZeroI64::new(-3).unwrap(),
+    };
+    let xml = concat!(
+        "<Wide>",
+        "<i_128>-170141183460469231731687303715884105728</i_128>",
+        "<u_128>340282366920938463463374607431768211455</u_128>",
+        "<nz_u32>7</nz_u32>",
+        "<nz_i64>-3</nz_i64>",
+        "</Wide>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = "<Wide><i_128>+1</i_128><u_128>\n +2\t</u_128><nz_u32>+7</nz_u32><nz_i64> -3 </nz_i64></Wide>";
+    let v = from_str::<Wide>(xml).unwrap();
+    assert_eq!((v.i_128, v.u_128, v.nz_u32.get()), (1, 2, 7));
+
+    let xml = "<Wide><i_128>1</i_128><u_128>2</u_128><nz_u32>0</nz_u32><nz_i64>1</nz_i64></Wide>";
+    assert!(matches!(
+        from_str::<Wide>(xml).unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+}
+
+#[derive(FromXml, ToXml, PartialEq, Debug)]
+struct Floats {
+    single: f32,
+    double: f64,
+}
+
+fn floats(xml: &str) -> Result<Floats, Error> {
+    from_str(&format!(
+        "<Floats><single>0</single><double>{xml}</double></Floats>"
+    ))
+}
+
+fn double(value: f64) -> String {
+    let xml = to_string(&Floats {
+        single: 0.0,
+        double: value,
+    })
+    .unwrap();
+    let start = xml.find("<double>").unwrap() + "<double>".len();
+    xml[start..xml.len() - "</double></Floats>".len()].to_owned()
+}
+
+#[test]
+fn xsd_floats() {
+    assert_eq!(floats("INF").unwrap().double, f64::INFINITY);
+    assert_eq!(floats(" +INF ").unwrap().double, f64::INFINITY);
+    assert_eq!(floats("-INF").unwrap().double, f64::NEG_INFINITY);
+    assert!(floats("NaN").unwrap().double.is_nan());
+    assert_eq!(floats("+1.5E2").unwrap().double, 150.0);
+    assert_eq!(floats(".5e-1").unwrap().double, 0.05);
+
+    for invalid in ["inf", "infinity", "nan", "-NaN", "1,5"] {
+        assert!(
+            matches!(floats(invalid).unwrap_err(), Error::UnexpectedValue(_)),
+            "{invalid:?}"
+        );
+    }
+
+    assert_eq!(double(f64::INFINITY), "INF");
+    assert_eq!(double(f64::NEG_INFINITY), "-INF");
+    assert_eq!(double(f64::NAN), "NaN");
+    assert_eq!(double(1.5), "1.5E0");
+    assert_eq!(double(-150.0), "-1.5E2");
+    assert_eq!(double(1e-7), "1.0E-7");
+    assert_eq!(double(0.0), "0.0E0");
+    assert_eq!(double(-0.0), "-0.0E0");
+    assert_eq!(double(1e300), "1.0E300");
+    assert_eq!(double(-2.5e-10), "-2.5E-10");
+    assert_eq!(double(5e-324), "5.0E-324");
+    assert_eq!(double(f64::MAX), "1.7976931348623157E308");
+    assert_eq!(double(1e16), "1.0E16");
+
+    // All output round-trips, including the sign of zero
+    for value in [0.0, -0.0, 5e-324, -2.2e-308, f64::MAX, f64::MIN, 1e16, 1e-7] {
+        let parsed = floats(&double(value)).unwrap().double;
+        assert_eq!(parsed.to_bits(), value.to_bits(), "{value:?}");
+    }
+
+    let v = Floats {
+        single: f32::MAX,
+        double: f64::MIN_POSITIVE,
+    };
+    assert_eq!(v, from_str(&to_string(&v).unwrap()).unwrap());
+}
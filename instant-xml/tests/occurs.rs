@@ -0,0 +1,164 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Playlist {
+    #[xml(rename = "track", min_occurs = 1, max_occurs = 3)]
+    tracks: Vec<String>,
+    #[xml(rename = "tag", max_occurs = 2, wrapper = "tags")]
+    tags: Vec<String>,
+}
+
+#[test]
+fn deserialize() {
+    let xml = "<Playlist><track>a</track><track>b</track><tags><tag>x</tag></tags></Playlist>";
+    assert_eq!(
+        from_str::<Playlist>(xml).unwrap(),
+        Playlist {
+            tracks: vec!["a".to_owned(), "b".to_owned()],
+            tags: vec!["x".to_owned()],
+        }
+    );
+
+    let xml = "<Playlist><tags><tag>x</tag></tags></Playlist>";
+    assert_eq!(
+        from_str::<Playlist>(xml).unwrap_err(),
+        Error::TooFewOccurrences {
+            field: "Playlist::tracks",
+            count: 0,
+            min: 1,
+        }
+    );
+
+    // The error is reported as soon as the maximum is exceeded, even if the input is malformed
+    let xml = "<Playlist><track>a</track><track>b</track><track>c</track><track>d</track><track>";
+    assert_eq!(
+        from_str::<Playlist>(xml).unwrap_err(),
+        Error::TooManyOccurrences {
+            field: "Playlist::tracks",
+            count: 4,
+            max: 3,
+        }
+    );
+
+    let xml =
+        "<Playlist><track>a</track><tags><tag>x</tag><tag>y</tag><tag>z</tag></tags></Playlist>";
+    assert_eq!(
+        from_str::<Playlist>(xml).unwrap_err(),
+        Error::TooManyOccurrences {
+            field: "Playlist::tags",
+            count: 3,
+            max: 2,
+        }
+    );
+}
+
+#[test]
+fn serialize() {
+    let playlist = Playlist {
+        tracks: vec!["a".to_owned()],
+        tags: vec![],
+    };
+    assert_eq!(
+        to_string(&playlist).unwrap(),
+        "<Playlist><track>a</track><tags></tags></Playlist>"
+    );
+
+    let playlist = Playlist {
+        tracks: vec![],
+        tags: vec![],
+    };
+    assert_eq!(
+        to_string(&playlist).unwrap_err(),
+        Error::TooFewOccurrences {
+            field: "Playlist::tracks",
+            count: 0,
+            min: 1,
+        }
+    );
+
+    let playlist = Playlist {
+        tracks: vec!["a".to_owned()],
+        tags: vec!["x".to_owned(), "y".to_owned(), "z".to_owned()],
+    };
+    assert_eq!(
+        to_string(&playlist).unwrap_err(),
+        Error::TooManyOccurrences {
+            field: "Playlist::tags",
+            count: 3,
+            max: 2,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(transparent)]
+struct Items {
+    #[xml(max_occurs = 2)]
+    item: Vec<Item>,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Item {
+    #[xml(attribute)]
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Order {
+    items: Items,
+}
+
+#[test]
+fn inline() {
+    let order = Order {
+        items: Items {
+            item: vec![Item { id: 1 }, Item { id: 2 }],
+        },
+    };
+    let xml = r#"<Order><Item id="1" /><Item id="2" /></Order>"#;
+    assert_eq!(to_string(&order).unwrap(), xml);
+    assert_eq!(from_str::<Order>(xml).unwrap(), order);
+
+    let xml = r#"<Order><Item id="1" /><Item id="2" /><Item id="3" /></Order>"#;
+    assert_eq!(
+        from_str::<Order>(xml).unwrap_err(),
+        Error::TooManyOccurrences {
+            field: "Items::item",
+            count: 3,
+            max: 2,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Survey {
+    #[xml(rename = "answer", max_occurs = 2)]
+    answers: Option<Vec<u8>>,
+}
+
+#[test]
+fn optional() {
+    let xml = "<Survey><answer>1</answer><answer>2</answer></Survey>";
+    let survey = Survey {
+        answers: Some(vec![1, 2]),
+    };
+    assert_eq!(to_string(&survey).unwrap(), xml);
+    assert_eq!(from_str::<Survey>(xml).unwrap(), survey);
+
+    let xml = "<Survey><answer>1</answer><answer>2</answer><answer>3</answer></Survey>";
+    assert_eq!(
+        from_str::<Survey>(xml).unwrap_err(),
+        Error::TooManyOccurrences {
+            field: "Survey::answers",
+            count: 3,
+            max: 2,
+        }
+    );
+
+    let survey = Survey {
+        answers: Some(vec![1, 2, 3]),
+    };
+    assert!(to_string(&survey).is_err());
+}
@@ -0,0 +1,89 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, Error, FromXml};
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(ordered)]
+struct Address {
+    #[xml(attribute)]
+    kind: String,
+    street: String,
+    #[xml(rename = "line")]
+    lines: Vec<String>,
+    city: Option<String>,
+    country: String,
+}
+
+#[test]
+fn in_order() {
+    let xml = r#"<Address kind="home"><street>Main</street><line>1</line><line>2</line><city>Amsterdam</city><country>NL</country></Address>"#;
+    assert_eq!(
+        from_str::<Address>(xml).unwrap(),
+        Address {
+            kind: "home".to_owned(),
+            street: "Main".to_owned(),
+            lines: vec!["1".to_owned(), "2".to_owned()],
+            city: Some("Amsterdam".to_owned()),
+            country: "NL".to_owned(),
+        }
+    );
+
+    // Optional and repeated fields may be skipped, unknown elements are ignored
+    let xml =
+        r#"<Address kind="home"><street>Main</street><unknown /><country>NL</country></Address>"#;
+    assert_eq!(
+        from_str::<Address>(xml).unwrap(),
+        Address {
+            kind: "home".to_owned(),
+            street: "Main".to_owned(),
+            lines: vec![],
+            city: None,
+            country: "NL".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn out_of_order() {
+    let xml = r#"<Address kind="home"><street>Main</street><country>NL</country><city>Amsterdam</city></Address>"#;
+    assert_eq!(
+        from_str::<Address>(xml).unwrap_err(),
+        Error::OutOfOrder {
+            element: "city".to_owned(),
+            field: "Address::city",
+            expected: "country",
+        }
+    );
+
+    let xml = r#"<Address kind="home"><street>Main</street><line>1</line><city>Amsterdam</city><line>2</line><country>NL</country></Address>"#;
+    assert_eq!(
+        from_str::<Address>(xml).unwrap_err(),
+        Error::OutOfOrder {
+            element: "line".to_owned(),
+            field: "Address::lines",
+            expected: "city or country",
+        }
+    );
+    assert_eq!(
+        from_str::<Address>(xml).unwrap_err().to_string(),
+        "unexpected element `line` for Address::lines, expected city or country"
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+struct Unordered {
+    first: u8,
+    second: u8,
+}
+
+#[test]
+fn unordered() {
+    let xml = "<Unordered><second>2</second><first>1</first></Unordered>";
+    assert_eq!(
+        from_str::<Unordered>(xml).unwrap(),
+        Unordered {
+            first: 1,
+            second: 2
+        }
+    );
+}
@@ -0,0 +1,125 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, from_str_as, to_string, to_string_as, Error, FromXml, Id, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(ns("urn:payload"))]
+struct Payload {
+    #[xml(attribute)]
+    id: u32,
+    status: String,
+    detail: Detail,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(ns("urn:payload"))]
+struct Detail {
+    code: u16,
+}
+
+fn payload() -> Payload {
+    Payload {
+        id: 1,
+        status: "ok".to_owned(),
+        detail: Detail { code: 200 },
+    }
+}
+
+#[test]
+fn name() {
+    let request = Id {
+        ns: "urn:payload",
+        name: "Request",
+    };
+
+    let xml = r#"<Request xmlns="urn:payload" id="1"><status>ok</status><Detail><code>200</code></Detail></Request>"#;
+    assert_eq!(to_string_as(&payload(), request).unwrap(), xml);
+    assert_eq!(from_str_as::<Payload>(xml, request).unwrap(), payload());
+
+    // The type's own name no longer matches, nor does the override without it
+    let xml = to_string(&payload()).unwrap();
+    assert!(matches!(
+        from_str_as::<Payload>(&xml, request).unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+    let xml = to_string_as(&payload(), request).unwrap();
+    assert!(from_str::<Payload>(&xml).is_err());
+}
+
+#[test]
+fn namespace() {
+    let response = Id {
+        ns: "urn:tenant",
+        name: "Response",
+    };
+
+    // Child elements keep the namespace of their own type
+    let xml = r#"<Response xmlns="urn:tenant" id="1"><status xmlns="urn:payload">ok</status><Detail xmlns="urn:payload"><code>200</code></Detail></Response>"#;
+    assert_eq!(to_string_as(&payload(), response).unwrap(), xml);
+    assert_eq!(from_str_as::<Payload>(xml, response).unwrap(), payload());
+}
+
+#[test]
+fn scalar() {
+    let count = Id {
+        ns: "",
+        name: "count",
+    };
+
+    assert_eq!(to_string_as(&3u8, count).unwrap(), "<count>3</count>");
+    assert_eq!(from_str_as::<u8>("<count>3</count>", count).unwrap(), 3);
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(forward)]
+enum Message {
+    Payload(Payload),
+    Detail(Detail),
+}
+
+#[test]
+fn forward() {
+    let request = Id {
+        ns: "urn:payload",
+        name: "Request",
+    };
+
+    let xml = r#"<Request xmlns="urn:payload"><code>200</code></Request>"#;
+    let message = Message::Detail(Detail { code: 200 });
+    assert_eq!(to_string_as(&message, request).unwrap(), xml);
+
+    // Without an override, variants keep their own name
+    let xml = r#"<Detail xmlns="urn:payload"><code>200</code></Detail>"#;
+    assert_eq!(to_string(&message).unwrap(), xml);
+    assert_eq!(from_str::<Message>(xml).unwrap(), message);
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(choice, rename_all = "lowercase")]
+enum State {
+    Active,
+    Inactive,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(ns("urn:payload"))]
+struct Current(State);
+
+#[test]
+fn choice() {
+    let response = Id {
+        ns: "urn:tenant",
+        name: "Response",
+    };
+
+    // Variants keep the namespace of the type they are nested in
+    let xml = r#"<Response xmlns="urn:tenant"><active xmlns="urn:payload" /></Response>"#;
+    assert_eq!(
+        to_string_as(&Current(State::Active), response).unwrap(),
+        xml
+    );
+    assert_eq!(
+        from_str_as::<Current>(xml, response).unwrap(),
+        Current(State::Active)
+    );
+}
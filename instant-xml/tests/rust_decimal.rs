@@ -0,0 +1,49 @@
+#![cfg(feature = "rust_decimal")]
+
+use rust_decimal::Decimal;
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, PartialEq, FromXml, ToXml)]
+struct Price {
+    #[xml(attribute)]
+    currency: String,
+    #[xml(direct)]
+    amount: Decimal,
+}
+
+#[test]
+fn decimal() {
+    let v = Price {
+        currency: "EUR".to_owned(),
+        amount: Decimal::new(1050, 2),
+    };
+    let xml = r#"<Price currency="EUR">10.50</Price>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Price>(xml).unwrap(), v);
+
+    for (input, expected) in [
+        ("+10.50", "10.50"),
+        ("-.5", "-0.5"),
+        ("1.", "1"),
+        (" 7 ", "7"),
+        ("-0", "0"),
+    ] {
+        let xml = format!(r#"<Price currency="EUR">{input}</Price>"#);
+        assert_eq!(
+            from_str::<Price>(&xml).unwrap().amount.to_string(),
+            expected
+        );
+    }
+
+    for input in ["1e5", "1_000", "1.2.3", "."] {
+        let xml = format!(r#"<Price currency="EUR">{input}</Price>"#);
+        assert_eq!(
+            from_str::<Price>(&xml).unwrap_err(),
+            Error::UnexpectedValue(format!(
+                "unable to parse number rust_decimal::decimal::Decimal from `{input}` for Price::amount"
+            ))
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(scalar)]
+struct Sku(String);
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(scalar)]
+struct Quantity(u32);
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(scalar)]
+struct Label<'a>(Cow<'a, str>);
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(scalar)]
+struct Version {
+    major: u16,
+    minor: u16,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for Version {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = s.split_once('.').ok_or("missing dot")?;
+        Ok(Self {
+            major: major.parse().map_err(|_| "invalid major")?,
+            minor: minor.parse().map_err(|_| "invalid minor")?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Line<'a> {
+    #[xml(attribute)]
+    sku: Sku,
+    #[xml(attribute)]
+    version: Version,
+    quantity: Quantity,
+    label: Option<Label<'a>>,
+    #[xml(direct)]
+    note: Sku,
+}
+
+#[test]
+fn scalar_structs() {
+    let v = Line {
+        sku: Sku("A&B".into()),
+        version: Version { major: 1, minor: 2 },
+        quantity: Quantity(3),
+        label: Some(Label("x".into())),
+        note: Sku("hi".into()),
+    };
+    let xml =
+        r#"<Line sku="A&amp;B" version="1.2"><quantity>3</quantity><label>x</label>hi</Line>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Line<'_>>(xml).unwrap(), v);
+
+    let xml = r#"<Line sku="A" version="1.2"><quantity>3</quantity></Line>"#;
+    assert_eq!(from_str::<Line<'_>>(xml).unwrap().label, None);
+
+    assert!(matches!(
+        from_str::<Line<'_>>(r#"<Line sku="A" version="1"><quantity>3</quantity></Line>"#)
+            .unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+    assert_eq!(
+        from_str::<Line<'_>>(r#"<Line sku="A" version="1.2"></Line>"#).unwrap_err(),
+        Error::MissingValue("Line::quantity")
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct Rgb(u8, u8, u8);
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = u32::from_str_radix(s.trim_start_matches('#'), 16)?;
+        Ok(Self((hex >> 16) as u8, (hex >> 8) as u8, hex as u8))
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Style {
+    #[xml(attribute, display_fromstr)]
+    color: Rgb,
+    #[xml(display_fromstr)]
+    background: Option<Rgb>,
+    #[xml(direct, display_fromstr)]
+    border: Rgb,
+}
+
+#[test]
+fn display_fromstr() {
+    let v = Style {
+        color: Rgb(255, 0, 16),
+        background: Some(Rgb(0, 0, 0)),
+        border: Rgb(1, 2, 3),
+    };
+    let xml = r##"<Style color="#ff0010"><background>#000000</background>#010203</Style>"##;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Style>(xml).unwrap(), v);
+
+    let v = Style {
+        background: None,
+        ..v
+    };
+    let xml = r##"<Style color="#ff0010">#010203</Style>"##;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Style>(xml).unwrap(), v);
+
+    assert!(matches!(
+        from_str::<Style>(r##"<Style color="red">#010203</Style>"##).unwrap_err(),
+        Error::UnexpectedValue(msg) if msg.ends_with("for Style::color")
+    ));
+}
+
+#[derive(Debug, PartialEq)]
+struct Quoted(String);
+
+impl fmt::Display for Quoted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.0)
+    }
+}
+
+impl FromStr for Quoted {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s.strip_prefix('"').and_then(|s| s.strip_suffix('"'));
+        Ok(Self(inner.ok_or("missing quotes")?.to_owned()))
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(scalar)]
+struct Condition {
+    left: String,
+    right: String,
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}<{}", self.left, self.right)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (left, right) = s.split_once('<').ok_or("missing operator")?;
+        Ok(Self {
+            left: left.to_owned(),
+            right: right.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Rule {
+    #[xml(attribute, display_fromstr)]
+    name: Quoted,
+    #[xml(display_fromstr)]
+    note: Quoted,
+    condition: Condition,
+}
+
+#[test]
+fn display_escaped() {
+    // `Display` output containing markup characters is escaped like a string
+    let v = Rule {
+        name: Quoted("x&y".to_owned()),
+        note: Quoted("<i>&".to_owned()),
+        condition: Condition {
+            left: "a".to_owned(),
+            right: "b".to_owned(),
+        },
+    };
+    let xml = concat!(
+        r#"<Rule name="&quot;x&amp;y&quot;"><note>&quot;&lt;i&gt;&amp;&quot;</note>"#,
+        "<condition>a&lt;b</condition></Rule>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Rule>(xml).unwrap(), v);
+}
@@ -0,0 +1,223 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::de::Limits;
+use instant_xml::{AnyAttribute, Error, FromXml, StreamEvent, StreamHeader, StreamParser};
+
+const STREAMS: &str = "http://etherx.jabber.org/streams";
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(forward)]
+enum Stanza {
+    Features(Features),
+    Message(Message),
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(rename = "features", ns(STREAMS))]
+struct Features {
+    #[xml(ns("urn:ietf:params:xml:ns:xmpp-bind"))]
+    bind: Option<Bind>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(rename = "bind", ns("urn:ietf:params:xml:ns:xmpp-bind"))]
+struct Bind;
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(rename = "message", ns("jabber:client"))]
+struct Message {
+    #[xml(attribute)]
+    to: String,
+    body: Option<String>,
+}
+
+const INPUT: &str = r#"<?xml version='1.0'?>
+<stream:stream xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams' to='example.com' version='1.0'>
+  <stream:features><bind xmlns='urn:ietf:params:xml:ns:xmpp-bind'/></stream:features>
+  <!-- keepalive -->
+  <message to='juliet@example.com'><body>Wherefore art thou? 🌹</body></message>
+  <message to='romeo@example.net'/>
+</stream:stream>"#;
+
+fn events(parser: &mut StreamParser<Stanza>) -> Vec<Result<StreamEvent<Stanza>, Error>> {
+    let mut events = Vec::new();
+    while let Some(event) = parser.next_event() {
+        events.push(event);
+    }
+    events
+}
+
+#[test]
+fn xmpp() {
+    // Push the input one byte at a time, splitting multi-byte characters
+    let mut parser = StreamParser::<Stanza>::new();
+    let mut all = Vec::new();
+    for byte in INPUT.as_bytes() {
+        parser.push(&[*byte]).unwrap();
+        all.extend(events(&mut parser));
+    }
+
+    let header = StreamHeader {
+        ns: STREAMS.to_owned(),
+        name: "stream".to_owned(),
+        attributes: vec![
+            AnyAttribute {
+                ns: Cow::Borrowed(""),
+                name: Cow::Borrowed("to"),
+                value: Cow::Borrowed("example.com"),
+            },
+            AnyAttribute {
+                ns: Cow::Borrowed(""),
+                name: Cow::Borrowed("version"),
+                value: Cow::Borrowed("1.0"),
+            },
+        ],
+        namespaces: BTreeMap::from([
+            (String::new(), "jabber:client".to_owned()),
+            ("stream".to_owned(), STREAMS.to_owned()),
+        ]),
+    };
+    assert_eq!(parser.header(), Some(&header));
+
+    let all = all.into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        all,
+        vec![
+            StreamEvent::Open(header),
+            StreamEvent::Stanza(Stanza::Features(Features { bind: Some(Bind) })),
+            StreamEvent::Stanza(Stanza::Message(Message {
+                to: "juliet@example.com".to_owned(),
+                body: Some("Wherefore art thou? 🌹".to_owned()),
+            })),
+            StreamEvent::Stanza(Stanza::Message(Message {
+                to: "romeo@example.net".to_owned(),
+                body: None,
+            })),
+            StreamEvent::Close,
+        ]
+    );
+}
+
+#[test]
+fn errors() {
+    // Invalid child elements are reported without ending the stream
+    let mut parser = StreamParser::<Stanza>::new();
+    parser
+        .push(b"<stream:stream xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams'>")
+        .unwrap();
+    parser
+        .push(b"<iq type='get'/><message to='a'><body>hi</body></message>")
+        .unwrap();
+
+    let events = events(&mut parser);
+    assert!(matches!(events[0], Ok(StreamEvent::Open(_))));
+    assert!(events[1].is_err());
+    assert!(matches!(
+        events[2],
+        Ok(StreamEvent::Stanza(Stanza::Message(_)))
+    ));
+    assert_eq!(events.len(), 3);
+
+    // Errors in the structure of the stream end it
+    parser.push(b"text</stream:stream>").unwrap();
+    assert!(matches!(
+        parser.next_event(),
+        Some(Err(Error::UnexpectedNode(_)))
+    ));
+    assert!(parser.next_event().is_none());
+
+    let mut parser = StreamParser::<Stanza>::new();
+    parser
+        .push(b"<stream:stream xmlns:stream='http://etherx.jabber.org/streams'></stream>")
+        .unwrap();
+    assert!(matches!(
+        parser.next_event(),
+        Some(Ok(StreamEvent::Open(_)))
+    ));
+    assert!(matches!(
+        parser.next_event(),
+        Some(Err(Error::UnexpectedState(_)))
+    ));
+
+    let mut parser = StreamParser::<Stanza>::new();
+    assert!(parser.push(&[0xff]).is_err());
+}
+
+const HEADER: &[u8] =
+    b"<stream:stream xmlns='jabber:client' xmlns:stream='http://etherx.jabber.org/streams'>";
+
+#[test]
+fn invalid_utf8() {
+    // Input before the invalid sequence is still parsed, then the stream ends
+    let mut parser = StreamParser::<Stanza>::new();
+    parser.push(HEADER).unwrap();
+    assert!(parser
+        .push(b"<message to='a'/><message to='b'>\xff</message>")
+        .is_err());
+    assert!(parser.push(b"<message to='c'/>").is_err());
+
+    let events = events(&mut parser);
+    assert_eq!(events.len(), 3);
+    assert!(matches!(events[0], Ok(StreamEvent::Open(_))));
+    match &events[1] {
+        Ok(StreamEvent::Stanza(Stanza::Message(message))) => assert_eq!(message.to, "a"),
+        event => panic!("unexpected event: {event:?}"),
+    }
+    assert_eq!(
+        events[2],
+        Err(Error::Other("invalid UTF-8 in stream".to_owned()))
+    );
+}
+
+#[test]
+fn limits() {
+    // Nesting is limited before the child element is complete
+    let mut limits = Limits::default();
+    limits.max_levels = 4;
+    let mut parser = StreamParser::<Stanza>::new().with_limits(limits);
+    parser.push(HEADER).unwrap();
+    parser.push(b"<message to='a'><a><b>").unwrap();
+    assert!(matches!(
+        parser.next_event(),
+        Some(Ok(StreamEvent::Open(_)))
+    ));
+    assert!(parser.next_event().is_none());
+    parser.push(b"<c>").unwrap();
+    assert_eq!(
+        parser.next_event().unwrap().unwrap_err(),
+        Error::Other("maximum number of nested element levels exceeded".to_owned())
+    );
+    assert!(parser.next_event().is_none());
+
+    // Incomplete stanzas cannot grow beyond the maximum size
+    let mut parser = StreamParser::<Stanza>::new().with_max_stanza_size(128);
+    parser.push(HEADER).unwrap();
+    parser.push(b"<message to='a'><body>").unwrap();
+    assert!(matches!(
+        parser.next_event(),
+        Some(Ok(StreamEvent::Open(_)))
+    ));
+    assert!(parser.next_event().is_none());
+    parser.push(&[b'x'; 128]).unwrap();
+    assert_eq!(
+        parser.next_event().unwrap().unwrap_err(),
+        Error::Other("maximum stanza size exceeded".to_owned())
+    );
+
+    // Complete stanzas are checked as well
+    let mut parser = StreamParser::<Stanza>::new().with_max_stanza_size(128);
+    parser.push(HEADER).unwrap();
+    parser.push(b"<message to='a'/>").unwrap();
+    parser
+        .push(format!("<message to='{}'/>", "a".repeat(128)).as_bytes())
+        .unwrap();
+    let events = events(&mut parser);
+    assert!(matches!(events[1], Ok(StreamEvent::Stanza(_))));
+    assert_eq!(
+        events[2],
+        Err(Error::Other("maximum stanza size exceeded".to_owned()))
+    );
+}
@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, Id, ToXml};
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(any_name)]
+struct Price {
+    #[xml(tag_name)]
+    currency: String,
+    #[xml(direct)]
+    amount: f64,
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Product {
+    name: String,
+    prices: Vec<Price>,
+}
+
+#[test]
+fn dynamic() {
+    let product = Product {
+        name: "widget".to_owned(),
+        prices: vec![
+            Price {
+                currency: "price-USD".to_owned(),
+                amount: 9.5,
+            },
+            Price {
+                currency: "price-EUR".to_owned(),
+                amount: 8.75,
+            },
+        ],
+    };
+
+    let xml = "<Product><name>widget</name><price-USD>9.5E0</price-USD><price-EUR>8.75E0</price-EUR></Product>";
+    assert_eq!(to_string(&product).unwrap(), xml);
+    assert_eq!(from_str::<Product>(xml).unwrap(), product);
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Parent {
+    price: Price,
+    other: String,
+}
+
+#[test]
+fn siblings() {
+    // Elements matching another field are not taken by the `any_name` field
+    let xml = "<Parent><price-USD>3.0E0</price-USD><other>x</other></Parent>";
+    let parent = Parent {
+        price: Price {
+            currency: "price-USD".to_owned(),
+            amount: 3.0,
+        },
+        other: "x".to_owned(),
+    };
+    assert_eq!(from_str::<Parent>(xml).unwrap(), parent);
+    assert_eq!(to_string(&parent).unwrap(), xml);
+
+    let xml = "<Parent><other>x</other><price-USD>3</price-USD></Parent>";
+    assert_eq!(from_str::<Parent>(xml).unwrap(), parent);
+
+    let xml = "<Product><price-USD>1</price-USD><name>widget</name></Product>";
+    assert_eq!(
+        from_str::<Product>(xml).unwrap(),
+        Product {
+            name: "widget".to_owned(),
+            prices: vec![Price {
+                currency: "price-USD".to_owned(),
+                amount: 1.0,
+            }],
+        }
+    );
+}
+
+#[test]
+fn invalid_name() {
+    for name in ["", "1st", "a b", "x&y", "p:q"] {
+        let price = Price {
+            currency: name.to_owned(),
+            amount: 1.0,
+        };
+        assert_eq!(
+            to_string(&price).unwrap_err(),
+            Error::UnexpectedValue(format!(
+                "tag name `{name}` for Price::currency is not a valid element name"
+            ))
+        );
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+#[xml(any_name, ns("urn:default"))]
+struct Entry<'a> {
+    #[xml(tag_name)]
+    id: Id<'a>,
+    #[xml(attribute)]
+    key: Cow<'a, str>,
+}
+
+#[test]
+fn namespaced() {
+    let xml = r#"<custom xmlns="urn:custom" key="a" />"#;
+    let entry = from_str::<Entry<'_>>(xml).unwrap();
+    assert_eq!(
+        entry,
+        Entry {
+            id: Id {
+                ns: "urn:custom",
+                name: "custom",
+            },
+            key: Cow::Borrowed("a"),
+        }
+    );
+    assert_eq!(to_string(&entry).unwrap(), xml);
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Named<'a> {
+    #[xml(tag_name)]
+    tag: Cow<'a, str>,
+    value: u8,
+}
+
+#[test]
+fn static_name() {
+    // Without `any_name`, only the container's own name matches
+    let xml = "<Named><value>1</value></Named>";
+    let named = from_str::<Named<'_>>(xml).unwrap();
+    assert_eq!(
+        named,
+        Named {
+            tag: Cow::Borrowed("Named"),
+            value: 1,
+        }
+    );
+    assert_eq!(to_string(&named).unwrap(), xml);
+    assert!(from_str::<Named<'_>>("<Other><value>1</value></Other>").is_err());
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "time")]
+
+use similar_asserts::assert_eq;
+use time::macros::{date, datetime, time};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, PartialEq, FromXml, ToXml)]
+struct Test<T> {
+    dt: T,
+}
+
+#[test]
+fn offset_datetime() {
+    let test = Test {
+        dt: datetime!(2022-11-21 21:17:23 +02:00),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23+02:00</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+    assert_eq!(from_str::<Test<OffsetDateTime>>(xml).unwrap(), test);
+
+    let test = Test {
+        dt: datetime!(2022-11-21 21:17:23.5 UTC),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23.5Z</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+    assert_eq!(from_str::<Test<OffsetDateTime>>(xml).unwrap(), test);
+}
+
+#[test]
+fn primitive_datetime() {
+    let test = Test {
+        dt: datetime!(2022-11-21 21:17:23),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+    assert_eq!(from_str::<Test<PrimitiveDateTime>>(xml).unwrap(), test);
+
+    let test = Test {
+        dt: datetime!(2022-11-21 21:17:23.125),
+    };
+    let xml = "<Test><dt>2022-11-21T21:17:23.125</dt></Test>";
+    assert_eq!(to_string(&test).unwrap(), xml);
+    assert_eq!(from_str::<Test<PrimitiveDateTime>>(xml).unwrap(), test);
+}
+
+#[test]
+fn date_and_time() {
+    let test = Test {
+        dt: date!(2024 - 01 - 01),
+    };
+    assert_eq!(
+        to_string(&test).unwrap(),
+        "<Test><dt>2024-01-01</dt></Test>"
+    );
+    for input in ["2024-01-01", "2024-01-01Z", "2024-01-01+05:30"] {
+        let xml = format!("<Test><dt>{input}</dt></Test>");
+        assert_eq!(from_str::<Test<Date>>(&xml).unwrap(), test, "{input}");
+    }
+
+    let test = Test {
+        dt: time!(13:20:00.5),
+    };
+    assert_eq!(
+        to_string(&test).unwrap(),
+        "<Test><dt>13:20:00.5</dt></Test>"
+    );
+    for input in ["13:20:00.5", "13:20:00.500Z", "13:20:00.5-05:00"] {
+        let xml = format!("<Test><dt>{input}</dt></Test>");
+        assert_eq!(from_str::<Test<Time>>(&xml).unwrap(), test, "{input}");
+    }
+
+    assert!(from_str::<Test<Date>>("<Test><dt>2024-01-01+15:00</dt></Test>").is_err());
+    assert!(from_str::<Test<Time>>("<Test><dt>13:20</dt></Test>").is_err());
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Formats {
+    #[xml(attribute, format = "[day]/[month]/[year]")]
+    date: Date,
+    #[xml(format = "[year][month][day][hour][minute][second]")]
+    stamp: PrimitiveDateTime,
+    #[xml(direct, format = "[hour].[minute]")]
+    time: Option<Time>,
+}
+
+#[test]
+fn formats() {
+    let v = Formats {
+        date: date!(2024 - 02 - 29),
+        stamp: datetime!(2024-03-01 12:30:45),
+        time: Some(time!(9:15)),
+    };
+    let xml = r#"<Formats date="29/02/2024"><stamp>20240301123045</stamp>09.15</Formats>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Formats>(xml).unwrap(), v);
+
+    assert_eq!(
+        from_str::<Formats>(&xml.replace("29/02/2024", "2024-02-29")).unwrap_err(),
+        Error::UnexpectedValue(
+            "unable to parse `2024-02-29` with format `[day]/[month]/[year]` for Formats::date"
+                .to_owned()
+        )
+    );
+}
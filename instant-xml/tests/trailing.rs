@@ -0,0 +1,60 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, Deserializer, Error, FromXml};
+
+#[derive(Debug, PartialEq, FromXml)]
+struct Root {
+    #[xml(attribute)]
+    id: u8,
+}
+
+#[test]
+fn misc() {
+    // Whitespace, comments and processing instructions may follow the root element
+    let xml = "<Root id=\"1\" />\n  <!-- end -->\n<?pi data?>\n";
+    assert_eq!(from_str::<Root>(xml).unwrap(), Root { id: 1 });
+}
+
+#[test]
+fn trailing() {
+    let err = from_str::<Root>(r#"<Root id="1" /><Other />"#).unwrap_err();
+    assert!(matches!(err, Error::TrailingContent(pos) if (pos.row, pos.col) == (1, 16)));
+
+    let err = from_str::<Root>("<Root id=\"1\"></Root>\ngarbage").unwrap_err();
+    assert!(matches!(err, Error::TrailingContent(pos) if (pos.row, pos.col) == (2, 1)));
+    assert_eq!(
+        err.to_string(),
+        "trailing content after root element at 2:1"
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(rename = "a")]
+struct Empty;
+
+#[test]
+fn trailing_element() {
+    // Every kind of trailing content is reported with its position
+    for (xml, col) in [
+        ("<a/><b/>", 5),
+        ("<a/> <b></b>", 6),
+        ("<a/><!DOCTYPE a>", 5),
+    ] {
+        let err = from_str::<Empty>(xml).unwrap_err();
+        assert!(
+            matches!(err, Error::TrailingContent(pos) if (pos.row, pos.col) == (1, col)),
+            "{xml}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn lenient() {
+    let xml = r#"<Root id="1" /><Other />"#;
+    let root = Deserializer::new(xml)
+        .unwrap()
+        .allow_trailing_content()
+        .deserialize::<Root>()
+        .unwrap();
+    assert_eq!(root, Root { id: 1 });
+}
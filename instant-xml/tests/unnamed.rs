@@ -1,6 +1,8 @@
+use std::fmt;
+
 use similar_asserts::assert_eq;
 
-use instant_xml::{from_str, to_string, FromXml, ToXml};
+use instant_xml::{from_str, to_string, Deserializer, Error, FromXml, Serializer, ToXml};
 
 #[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
 struct OneNumber(i32);
@@ -36,4 +38,157 @@ fn string_element() {
     let xml = r#"<StringElement>f42<Foo /></StringElement>"#;
     assert_eq!(xml, to_string(&v).unwrap());
     assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = r#"<StringElement>f42<Bar /></StringElement>"#;
+    assert_eq!(
+        from_str::<StringElement>(xml).unwrap_err(),
+        Error::UnexpectedTag("Bar for StringElement::1".to_owned())
+    );
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(rename = "price")]
+struct Price(
+    #[xml(attribute, rename = "currency")] String,
+    #[xml(attribute, rename = "exact")] Option<bool>,
+    #[xml(direct)] u32,
+);
+
+#[test]
+fn attributes() {
+    let v = Price("EUR".to_owned(), None, 42);
+    let xml = r#"<price currency="EUR">42</price>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let v = Price("EUR".to_owned(), Some(true), 42);
+    let xml = r#"<price currency="EUR" exact="true">42</price>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    // Unknown attributes are ignored
+    let xml = r#"<price currency="EUR" unit="cent">42</price>"#;
+    assert_eq!(Price("EUR".to_owned(), None, 42), from_str(xml).unwrap());
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Flag(#[xml(attribute, rename = "on")] bool);
+
+#[test]
+fn attributes_only() {
+    let v = Flag(true);
+    let xml = r#"<Flag on="true" />"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(ns("urn:example"))]
+struct Range(
+    #[xml(rename = "min")] i32,
+    #[xml(rename = "max")] i32,
+    #[xml(rename = "unit", ns("urn:units"))] Option<String>,
+);
+
+#[test]
+fn renamed() {
+    let v = Range(1, 5, None);
+    let xml = r#"<Range xmlns="urn:example"><min>1</min><max>5</max></Range>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let v = Range(1, 5, Some("m".to_owned()));
+    let xml = r#"<Range xmlns="urn:example"><min>1</min><max>5</max><unit xmlns="urn:units">m</unit></Range>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = r#"<Range xmlns="urn:example"><max>5</max><min>1</min></Range>"#;
+    assert_eq!(
+        from_str::<Range>(xml).unwrap_err(),
+        Error::UnexpectedTag("max for Range::0".to_owned())
+    );
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Switch(
+    #[xml(
+        attribute,
+        rename = "state",
+        serialize_with = "serialize_on_off",
+        deserialize_with = "deserialize_on_off"
+    )]
+    bool,
+);
+
+fn serialize_on_off<W: fmt::Write + ?Sized>(
+    value: &bool,
+    serializer: &mut Serializer<'_, W>,
+) -> Result<(), Error> {
+    serializer.write_str(if *value { "on" } else { "off" })
+}
+
+fn deserialize_on_off(
+    into: &mut Option<bool>,
+    field: &'static str,
+    deserializer: &mut Deserializer<'_, '_>,
+) -> Result<(), Error> {
+    *into = match deserializer.take_str()?.as_deref() {
+        Some("on") => Some(true),
+        Some("off") => Some(false),
+        _ => return Err(Error::UnexpectedValue(format!("invalid value for {field}"))),
+    };
+    Ok(())
+}
+
+#[test]
+fn with() {
+    let v = Switch(true);
+    let xml = r#"<Switch state="on" />"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Bar {
+    #[xml(attribute)]
+    id: u32,
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Pair {
+    pair: (Foo, Bar),
+    #[xml(rename = "coord")]
+    coords: (i32, i32, Option<i32>),
+}
+
+#[test]
+fn tuples() {
+    let v = Pair {
+        pair: (Foo, Bar { id: 1 }),
+        coords: (3, 4, None),
+    };
+    let xml = r#"<Pair><Foo /><Bar id="1" /><coord>3</coord><coord>4</coord></Pair>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let v = Pair {
+        pair: (Foo, Bar { id: 1 }),
+        coords: (3, 4, Some(5)),
+    };
+    let xml =
+        r#"<Pair><Foo /><Bar id="1" /><coord>3</coord><coord>4</coord><coord>5</coord></Pair>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = r#"<Pair><Bar id="1" /><Foo /><coord>3</coord><coord>4</coord></Pair>"#;
+    assert_eq!(
+        from_str::<Pair>(xml).unwrap_err(),
+        Error::UnexpectedTag("Bar for Pair::pair".to_owned())
+    );
+
+    let xml = r#"<Pair><Foo /><Bar id="1" /><coord>3</coord><coord>4</coord><coord>5</coord><coord>6</coord></Pair>"#;
+    assert_eq!(
+        from_str::<Pair>(xml).unwrap_err(),
+        Error::DuplicateValue("Pair::coords")
+    );
 }
@@ -0,0 +1,40 @@
+#![cfg(feature = "url")]
+
+use similar_asserts::assert_eq;
+use url::Url;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, PartialEq, FromXml, ToXml)]
+struct Endpoint {
+    #[xml(attribute)]
+    base: Url,
+    callback: Url,
+}
+
+#[test]
+fn url() {
+    let v = Endpoint {
+        base: Url::parse("https://example.com/api/").unwrap(),
+        callback: Url::parse("https://example.com/cb?a=1&b=2").unwrap(),
+    };
+    let xml = concat!(
+        r#"<Endpoint base="https://example.com/api/">"#,
+        "<callback>https://example.com/cb?a=1&amp;b=2</callback></Endpoint>"
+    );
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Endpoint>(xml).unwrap(), v);
+
+    let padded = concat!(
+        r#"<Endpoint base=" https://example.com/api/ "><callback>"#,
+        "\n  https://example.com/cb?a=1&amp;b=2\n</callback></Endpoint>"
+    );
+    assert_eq!(from_str::<Endpoint>(padded).unwrap(), v);
+
+    assert_eq!(
+        from_str::<Endpoint>(r#"<Endpoint base="/relative"><callback /></Endpoint>"#).unwrap_err(),
+        Error::UnexpectedValue(
+            "unable to parse url::Url from `/relative` for Endpoint::base".to_owned()
+        )
+    );
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "uuid")]
+
+use similar_asserts::assert_eq;
+use uuid::Uuid;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, PartialEq, FromXml, ToXml)]
+struct Resource {
+    #[xml(attribute)]
+    id: Uuid,
+    parent: Option<Uuid>,
+}
+
+#[test]
+fn uuid() {
+    let v = Resource {
+        id: Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+        parent: None,
+    };
+    let xml = r#"<Resource id="67e55044-10b1-426f-9247-bb680e5fe0c8"></Resource>"#;
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(from_str::<Resource>(xml).unwrap(), v);
+
+    let upper = r#"<Resource id="67E55044-10B1-426F-9247-BB680E5FE0C8"><parent>67e5504410b1426f9247bb680e5fe0c8</parent></Resource>"#;
+    let parsed = from_str::<Resource>(upper).unwrap();
+    assert_eq!(parsed.id, v.id);
+    assert_eq!(parsed.parent, Some(v.id));
+
+    let padded = r#"<Resource id=" 67e55044-10b1-426f-9247-bb680e5fe0c8 "><parent>
+        67e55044-10b1-426f-9247-bb680e5fe0c8
+    </parent></Resource>"#;
+    let parsed = from_str::<Resource>(padded).unwrap();
+    assert_eq!(parsed.id, v.id);
+    assert_eq!(parsed.parent, Some(v.id));
+
+    assert_eq!(
+        from_str::<Resource>(r#"<Resource id="nope" />"#).unwrap_err(),
+        Error::UnexpectedValue(
+            "unable to parse uuid::Uuid from `nope` for Resource::id".to_owned()
+        )
+    );
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, Error, FromXml};
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(validate = "Range::check")]
+struct Range {
+    #[xml(attribute)]
+    min: u32,
+    #[xml(attribute)]
+    max: u32,
+}
+
+impl Range {
+    fn check(&self) -> Result<(), String> {
+        match self.min <= self.max {
+            true => Ok(()),
+            false => Err(format!("min {} exceeds max {}", self.min, self.max)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+struct Team {
+    #[xml(attribute, min_len = 1, max_len = 8)]
+    name: String,
+    #[xml(rename = "member", min_len = 1)]
+    members: Vec<String>,
+    #[xml(max_len = 3)]
+    code: Option<String>,
+    range: Range,
+}
+
+#[test]
+fn container() {
+    let xml = r#"<Range min="1" max="3" />"#;
+    assert_eq!(from_str::<Range>(xml).unwrap(), Range { min: 1, max: 3 });
+
+    let xml = r#"<Range min="4" max="3" />"#;
+    assert_eq!(
+        from_str::<Range>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Range",
+            message: "min 4 exceeds max 3".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn facets() {
+    let xml = r#"<Team name="rust"><member>ferris</member><Range min="1" max="2" /></Team>"#;
+    assert_eq!(
+        from_str::<Team>(xml).unwrap(),
+        Team {
+            name: "rust".to_owned(),
+            members: vec!["ferris".to_owned()],
+            code: None,
+            range: Range { min: 1, max: 2 },
+        }
+    );
+
+    let xml = r#"<Team name=""><member>ferris</member><Range min="1" max="2" /></Team>"#;
+    assert_eq!(
+        from_str::<Team>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Team::name",
+            message: "length 0 is less than the minimum of 1".to_owned(),
+        }
+    );
+
+    // Text length is measured in characters, not bytes
+    let xml = r#"<Team name="ŕüśţàćéà"><member>ferris</member><Range min="1" max="2" /></Team>"#;
+    assert!(from_str::<Team>(xml).is_ok());
+
+    let xml = r#"<Team name="rustaceans"><member>ferris</member><Range min="1" max="2" /></Team>"#;
+    assert_eq!(
+        from_str::<Team>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Team::name",
+            message: "length 10 exceeds the maximum of 8".to_owned(),
+        }
+    );
+
+    let xml = r#"<Team name="rust"><Range min="1" max="2" /></Team>"#;
+    assert_eq!(
+        from_str::<Team>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Team::members",
+            message: "length 0 is less than the minimum of 1".to_owned(),
+        }
+    );
+
+    let xml = r#"<Team name="rust"><member>ferris</member><code>RUST</code><Range min="1" max="2" /></Team>"#;
+    assert_eq!(
+        from_str::<Team>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Team::code",
+            message: "length 4 exceeds the maximum of 3".to_owned(),
+        }
+    );
+
+    let xml = r#"<Team name="rust"><member>ferris</member><Range min="2" max="1" /></Team>"#;
+    assert_eq!(
+        from_str::<Team>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Range",
+            message: "min 2 exceeds max 1".to_owned(),
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[xml(scalar, validate = "Even::check")]
+struct Even(u32);
+
+#[derive(Debug)]
+struct Odd(u32);
+
+impl fmt::Display for Odd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is odd", self.0)
+    }
+}
+
+impl Even {
+    fn check(&self) -> Result<(), Odd> {
+        match self.0 % 2 {
+            0 => Ok(()),
+            _ => Err(Odd(self.0)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+struct Counter {
+    #[xml(attribute)]
+    step: Even,
+}
+
+#[test]
+fn scalar() {
+    let xml = r#"<Counter step="2" />"#;
+    assert_eq!(from_str::<Counter>(xml).unwrap(), Counter { step: Even(2) });
+
+    let xml = r#"<Counter step="3" />"#;
+    assert_eq!(
+        from_str::<Counter>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Even",
+            message: "3 is odd".to_owned(),
+        }
+    );
+}
+
+#[cfg(feature = "regex")]
+#[derive(Debug, PartialEq, FromXml)]
+struct Country {
+    #[xml(attribute, pattern = "[A-Z]{2}")]
+    code: String,
+    #[xml(direct, pattern = "[A-Za-z ]+")]
+    name: String,
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn pattern() {
+    let xml = r#"<Country code="NL">The Netherlands</Country>"#;
+    assert_eq!(
+        from_str::<Country>(xml).unwrap(),
+        Country {
+            code: "NL".to_owned(),
+            name: "The Netherlands".to_owned(),
+        }
+    );
+
+    // Patterns must match the entire value
+    let xml = r#"<Country code="NLD">The Netherlands</Country>"#;
+    assert_eq!(
+        from_str::<Country>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Country::code",
+            message: "`NLD` does not match pattern `[A-Z]{2}`".to_owned(),
+        }
+    );
+
+    let xml = r#"<Country code="NL">Nederland!</Country>"#;
+    assert_eq!(
+        from_str::<Country>(xml).unwrap_err(),
+        Error::Validation {
+            field: "Country::name",
+            message: "`Nederland!` does not match pattern `[A-Za-z ]+`".to_owned(),
+        }
+    );
+}
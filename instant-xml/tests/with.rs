@@ -2,7 +2,7 @@ use std::fmt;
 
 use similar_asserts::assert_eq;
 
-use instant_xml::{to_string, Error, Serializer, ToXml};
+use instant_xml::{from_str, to_string, Error, FromXml, Serializer, ToXml};
 
 #[derive(ToXml)]
 struct Foo {
@@ -23,3 +23,107 @@ fn serialize_with() {
     let xml = r#"<Foo>foo: 42</Foo>"#;
     assert_eq!(xml, to_string(&v).unwrap());
 }
+
+mod hex {
+    use std::fmt;
+
+    use instant_xml::{Deserializer, Error, Serializer};
+
+    pub(crate) fn serialize<W: fmt::Write + ?Sized>(
+        value: &u32,
+        serializer: &mut Serializer<'_, W>,
+    ) -> Result<(), Error> {
+        serializer.write_str(&format_args!("{value:#x}"))
+    }
+
+    pub(crate) fn deserialize(
+        into: &mut Option<u32>,
+        field: &'static str,
+        deserializer: &mut Deserializer<'_, '_>,
+    ) -> Result<(), Error> {
+        if into.is_some() {
+            return Err(Error::DuplicateValue(field));
+        }
+
+        let Some(value) = deserializer.take_str()? else {
+            return Ok(());
+        };
+
+        match value.strip_prefix("0x").map(|v| u32::from_str_radix(v, 16)) {
+            Some(Ok(value)) => {
+                *into = Some(value);
+                Ok(())
+            }
+            _ => Err(Error::UnexpectedValue(format!(
+                "invalid hex value `{value}` for {field}"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml, ToXml)]
+struct Register {
+    #[xml(attribute, with = "hex")]
+    address: u32,
+    #[xml(direct, with = "hex")]
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+struct Registers {
+    #[xml(with = "hex")]
+    base: u32,
+    #[xml(deserialize_with = "hex::deserialize")]
+    mask: u32,
+}
+
+#[test]
+fn with_module() {
+    let v = Register {
+        address: 0x40,
+        value: 0xff,
+    };
+    let xml = r#"<Register address="0x40">0xff</Register>"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(from_str::<Register>(xml).unwrap(), v);
+
+    assert_eq!(
+        from_str::<Register>(r#"<Register address="64">0xff</Register>"#).unwrap_err(),
+        Error::UnexpectedValue("invalid hex value `64` for Register::address".to_owned())
+    );
+    assert_eq!(
+        from_str::<Register>(r#"<Register address="0x40"></Register>"#).unwrap_err(),
+        Error::MissingValue("Register::value")
+    );
+
+    let xml = "<Registers><base>0x1000</base><mask>0xf0</mask></Registers>";
+    assert_eq!(
+        from_str::<Registers>(xml).unwrap(),
+        Registers {
+            base: 0x1000,
+            mask: 0xf0
+        }
+    );
+}
+
+#[derive(ToXml)]
+struct Quoted {
+    #[xml(attribute, serialize_with = "serialize_quoted")]
+    label: String,
+}
+
+fn serialize_quoted<W: fmt::Write + ?Sized>(
+    value: &str,
+    serializer: &mut Serializer<'_, W>,
+) -> Result<(), Error> {
+    serializer.write_str(&format_args!("\"{value}\""))
+}
+
+#[test]
+fn escaped_attribute() {
+    let v = Quoted {
+        label: "<a> & <b>".to_owned(),
+    };
+    let xml = r#"<Quoted label="&quot;&lt;a&gt; &amp; &lt;b&gt;&quot;" />"#;
+    assert_eq!(xml, to_string(&v).unwrap());
+}
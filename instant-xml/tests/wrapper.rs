@@ -0,0 +1,119 @@
+use similar_asserts::assert_eq;
+
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Item {
+    #[xml(attribute)]
+    id: u32,
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+struct Order {
+    #[xml(wrapper = "items")]
+    items: Vec<Item>,
+    #[xml(wrapper = "tags", rename = "tag")]
+    tags: Vec<String>,
+    #[xml(wrapper = "notes")]
+    note: Option<Vec<String>>,
+}
+
+#[test]
+fn wrapper() {
+    let v = Order {
+        items: vec![Item { id: 1 }, Item { id: 2 }],
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        note: Some(vec![]),
+    };
+    let xml = concat!(
+        "<Order>",
+        r#"<items><Item id="1" /><Item id="2" /></items>"#,
+        "<tags><tag>a</tag><tag>b</tag></tags>",
+        "<notes></notes>",
+        "</Order>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    // Absent values skip the wrapper element entirely
+    let v = Order { note: None, ..v };
+    let xml = xml.replace("<notes></notes>", "");
+    assert_eq!(to_string(&v).unwrap(), xml);
+    assert_eq!(v, from_str(&xml).unwrap());
+
+    let v = Order {
+        items: vec![],
+        tags: vec![],
+        note: Some(vec!["fragile".to_owned()]),
+    };
+    let xml = "<Order><items></items><tags></tags><notes><note>fragile</note></notes></Order>";
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+}
+
+#[test]
+fn wrapper_empty_or_absent() {
+    let empty = Order {
+        items: vec![],
+        tags: vec![],
+        note: Some(vec![]),
+    };
+    assert_eq!(
+        empty,
+        from_str("<Order><items /><tags>\n</tags><notes /></Order>").unwrap()
+    );
+
+    // Only an absent wrapper yields `None`
+    let absent = Order {
+        items: vec![],
+        tags: vec![],
+        note: None,
+    };
+    assert_eq!(absent, from_str("<Order></Order>").unwrap());
+
+    // Unknown elements inside the wrapper are ignored
+    assert_eq!(
+        from_str::<Order>(r#"<Order><items><Other /><Item id="3" /></items></Order>"#).unwrap(),
+        Order {
+            items: vec![Item { id: 3 }],
+            tags: vec![],
+            note: None,
+        }
+    );
+
+    // Items outside the wrapper are not matched
+    assert_eq!(
+        from_str::<Order>(r#"<Order><Item id="3" /><tag>a</tag><note>b</note></Order>"#).unwrap(),
+        absent
+    );
+}
+
+#[test]
+fn wrapper_duplicate() {
+    assert_eq!(
+        from_str::<Order>(r#"<Order><items /><items><Item id="1" /></items></Order>"#).unwrap_err(),
+        Error::DuplicateValue("Order::items")
+    );
+}
+
+#[derive(Debug, Eq, FromXml, PartialEq, ToXml)]
+#[xml(ns("URI", bar = "BAR"))]
+struct NsOrder {
+    #[xml(wrapper = "entries", ns("BAR"))]
+    entry: Vec<u32>,
+}
+
+#[test]
+fn wrapper_ns() {
+    let v = NsOrder { entry: vec![1, 2] };
+    let xml = concat!(
+        r#"<NsOrder xmlns="URI" xmlns:bar="BAR">"#,
+        "<bar:entries><bar:entry>1</bar:entry><bar:entry>2</bar:entry></bar:entries>",
+        "</NsOrder>"
+    );
+    assert_eq!(xml, to_string(&v).unwrap());
+    assert_eq!(v, from_str(xml).unwrap());
+
+    let xml = r#"<NsOrder xmlns="URI"><entries><entry>1</entry></entries></NsOrder>"#;
+    assert_eq!(NsOrder { entry: vec![] }, from_str(xml).unwrap());
+}
@@ -0,0 +1,202 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use similar_asserts::assert_eq;
+
+use instant_xml::xsd::{
+    AnyUri, Base64Binary, Decimal, Duration, GYear, GYearMonth, HexBinary, NCName, Time, Token,
+};
+use instant_xml::{from_str, to_string, Error, FromXml, ToXml};
+
+fn canonical<T: FromStr<Err = Error> + Display>(input: &str) -> String {
+    match T::from_str(input) {
+        Ok(value) => value.to_string(),
+        Err(e) => panic!("failed to parse {input:?}: {e}"),
+    }
+}
+
+fn invalid<T: FromStr<Err = Error>>(input: &str) -> bool {
+    matches!(T::from_str(input), Err(Error::UnexpectedValue(_)))
+}
+
+#[test]
+fn strings() {
+    assert_eq!(canonical::<Token>("  a \t b\n\nc "), "a b c");
+    assert_eq!(
+        canonical::<AnyUri>(" http://example.com/a b "),
+        "http://example.com/a b"
+    );
+    assert_eq!(canonical::<NCName>(" _foo-bar.1 "), "_foo-bar.1");
+    assert_eq!(canonical::<NCName>("ünïcode"), "ünïcode");
+    for input in ["", "1abc", "a:b", "-a", "a b"] {
+        assert!(invalid::<NCName>(input), "{input:?}");
+    }
+}
+
+#[test]
+fn binary() {
+    assert_eq!(canonical::<HexBinary>(" 0fb7aB "), "0FB7AB");
+    assert_eq!(HexBinary::from_str("").unwrap().0, Vec::<u8>::new());
+    for input in ["0", "0g", "0 f"] {
+        assert!(invalid::<HexBinary>(input), "{input:?}");
+    }
+
+    assert_eq!(Base64Binary::from_str("").unwrap().0, Vec::<u8>::new());
+    for (bytes, encoded) in [
+        (&b"f"[..], "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"\xff\xfe\xfd", "//79"),
+    ] {
+        assert_eq!(Base64Binary(bytes.to_vec()).to_string(), encoded);
+        assert_eq!(Base64Binary::from_str(encoded).unwrap().0, bytes);
+    }
+
+    assert_eq!(canonical::<Base64Binary>(" Zm9v\n Yg= = "), "Zm9vYg==");
+    for input in ["Zg", "Zh==", "Zg==Zm9v", "Zm9v!", "===="] {
+        assert!(invalid::<Base64Binary>(input), "{input:?}");
+    }
+}
+
+#[test]
+fn decimal() {
+    for (input, expected) in [
+        ("+010.50", "10.5"),
+        ("-0.0", "0"),
+        ("1.", "1"),
+        (".25", "0.25"),
+        ("-007", "-7"),
+        (
+            "123456789012345678901234567890.5",
+            "123456789012345678901234567890.5",
+        ),
+    ] {
+        assert_eq!(canonical::<Decimal>(input), expected);
+    }
+
+    assert_eq!(Decimal::from_str("1.0").unwrap(), Decimal::from(1i64));
+    for input in ["", ".", "-", "1e5", "1.2.3", "INF", "+-1"] {
+        assert!(invalid::<Decimal>(input), "{input:?}");
+    }
+}
+
+#[test]
+fn duration() {
+    for (input, expected) in [
+        ("P1Y2M3DT4H5M6S", "P1Y2M3DT4H5M6S"),
+        ("P0Y14M", "P1Y2M"),
+        ("PT90M", "PT1H30M"),
+        ("PT36H", "P1DT12H"),
+        ("-PT1.500S", "-PT1.5S"),
+        ("-P0D", "PT0S"),
+        ("P1M", "P1M"),
+        ("PT0.000000001S", "PT0.000000001S"),
+    ] {
+        assert_eq!(canonical::<Duration>(input), expected);
+    }
+
+    let d = Duration::from_str("P1YT1.25S").unwrap();
+    assert_eq!((d.months(), d.seconds(), d.nanos()), (12, 1, 250_000_000));
+    for input in [
+        "",
+        "P",
+        "PT",
+        "1Y",
+        "P1S",
+        "PT1Y",
+        "P1M1Y",
+        "P1Y1Y",
+        "P1.5Y",
+        "PT.5S",
+        "P-1Y",
+        "P1YT",
+        "PT0.0000000001S",
+    ] {
+        assert!(invalid::<Duration>(input), "{input:?}");
+    }
+}
+
+#[test]
+fn dates_and_times() {
+    assert_eq!(canonical::<GYear>("2024"), "2024");
+    assert_eq!(canonical::<GYear>("-0044+00:00"), "-0044Z");
+    assert_eq!(canonical::<GYear>("12345-05:30"), "12345-05:30");
+    assert_eq!(GYear::from_str("2024+14:00").unwrap().offset(), Some(840));
+    for input in ["24", "02024", "2024+15:00", "2024Z+01:00", "2024+1:00"] {
+        assert!(invalid::<GYear>(input), "{input:?}");
+    }
+
+    assert_eq!(canonical::<GYearMonth>("2024-02Z"), "2024-02Z");
+    assert_eq!(GYearMonth::from_str("2024-12").unwrap().month(), 12);
+    for input in ["2024-13", "2024-00", "2024-1", "2024"] {
+        assert!(invalid::<GYearMonth>(input), "{input:?}");
+    }
+
+    assert_eq!(canonical::<Time>("13:20:00"), "13:20:00");
+    assert_eq!(canonical::<Time>("13:20:30.5000-05:00"), "13:20:30.5-05:00");
+    assert_eq!(canonical::<Time>("24:00:00Z"), "00:00:00Z");
+    let time = Time::from_str("01:02:03.004").unwrap();
+    assert_eq!(
+        (time.hour(), time.minute(), time.second(), time.nanos()),
+        (1, 2, 3, 4_000_000)
+    );
+    for input in [
+        "24:00:01",
+        "12:60:00",
+        "12:00",
+        "12:00:00.",
+        "1:00:00",
+        "12:00:60",
+        "00:00:0é",
+        "00:00:00é",
+    ] {
+        assert!(invalid::<Time>(input), "{input:?}");
+    }
+}
+
+#[derive(Debug, FromXml, PartialEq, ToXml)]
+struct Record {
+    #[xml(attribute)]
+    id: NCName,
+    #[xml(attribute)]
+    kind: Token,
+    link: AnyUri,
+    checksum: HexBinary,
+    data: Base64Binary,
+    amount: Decimal,
+    period: Duration,
+    year: GYear,
+    month: GYearMonth,
+    at: Time,
+}
+
+#[test]
+fn record() {
+    let xml = concat!(
+        r#"<Record id=" r1 " kind=" a  b "><link>http://a.example/?x=1&amp;y=2</link>"#,
+        "<checksum>ff00</checksum><data>\n  aGVs\n  bG8=\n</data>",
+        "<amount>0100.0</amount><period>PT120S</period><year>2024</year>",
+        "<month>2024-05+01:00</month><at>08:30:00</at></Record>"
+    );
+    let record = from_str::<Record>(xml).unwrap();
+    assert_eq!(record.data.0, b"hello");
+    assert_eq!(
+        to_string(&record).unwrap(),
+        concat!(
+            r#"<Record id="r1" kind="a b"><link>http://a.example/?x=1&amp;y=2</link>"#,
+            "<checksum>FF00</checksum><data>aGVsbG8=</data>",
+            "<amount>100</amount><period>PT2M</period><year>2024</year>",
+            "<month>2024-05+01:00</month><at>08:30:00</at></Record>"
+        )
+    );
+
+    assert!(matches!(
+        from_str::<Record>(&xml.replace("PT120S", "120")).unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+    assert!(matches!(
+        from_str::<Record>(&xml.replace("08:30:00", "00:00:0é")).unwrap_err(),
+        Error::UnexpectedValue(_)
+    ));
+}